@@ -6,11 +6,16 @@ use crate::client::Expires;
 use crate::devmode;
 use anyhow::Context;
 use core::fmt::{self, Debug, Formatter};
-use std::time::Duration;
+use rand::Rng;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::{ops::Deref, sync::Arc};
 use tokio::sync::RwLock;
 use url::Url;
 
+use store::TokenStore;
+
 #[derive(Clone, Debug, PartialEq, Eq, clap::Args)]
 #[command(next_help_heading = "OIDC client configuration")]
 pub struct OpenIdTokenProviderConfigArguments {
@@ -57,6 +62,139 @@ pub struct OpenIdTokenProviderConfigArguments {
         env = "OIDC_PROVIDER_SCOPES"
     )]
     pub scopes: Option<String>,
+    /// Initial access token used to register a new OAuth2 client via RFC 7591 Dynamic Client
+    /// Registration, instead of a pre-provisioned `--oidc-client-id`/`--oidc-client-secret`
+    #[arg(
+        id = "oidc_registration_token",
+        long = "oidc-registration-token",
+        env = "OIDC_PROVIDER_REGISTRATION_TOKEN"
+    )]
+    pub initial_access_token: Option<String>,
+    /// `client_name` to register with the issuer when using Dynamic Client Registration
+    #[arg(
+        id = "oidc_client_name",
+        long = "oidc-client-name",
+        env = "OIDC_PROVIDER_CLIENT_NAME",
+        default_value = "trustify"
+    )]
+    pub client_name: String,
+    /// OAuth2 grant type to use for the initial token exchange
+    #[arg(
+        id = "oidc_grant_type",
+        long = "oidc-grant-type",
+        env = "OIDC_PROVIDER_GRANT_TYPE",
+        default_value = "client-credentials"
+    )]
+    pub grant_type: GrantType,
+    /// Username for the `password` grant
+    #[arg(
+        id = "oidc_username",
+        long = "oidc-username",
+        env = "OIDC_PROVIDER_USERNAME",
+        requires = "oidc_password"
+    )]
+    pub username: Option<String>,
+    /// Password for the `password` grant
+    #[arg(
+        id = "oidc_password",
+        long = "oidc-password",
+        env = "OIDC_PROVIDER_PASSWORD",
+        requires = "oidc_username"
+    )]
+    pub password: Option<String>,
+    /// Seed refresh token for the `refresh-token` grant: skips the initial token exchange and
+    /// refreshes this token directly
+    #[arg(
+        id = "oidc_refresh_token",
+        long = "oidc-refresh-token",
+        env = "OIDC_PROVIDER_REFRESH_TOKEN"
+    )]
+    pub refresh_token: Option<String>,
+    /// Pre-obtained authorization code for the `authorization-code` grant
+    #[arg(
+        id = "oidc_authorization_code",
+        long = "oidc-authorization-code",
+        env = "OIDC_PROVIDER_AUTHORIZATION_CODE",
+        requires = "oidc_redirect_uri"
+    )]
+    pub authorization_code: Option<String>,
+    /// Redirect URI the authorization code was issued for
+    #[arg(
+        id = "oidc_redirect_uri",
+        long = "oidc-redirect-uri",
+        env = "OIDC_PROVIDER_REDIRECT_URI"
+    )]
+    pub redirect_uri: Option<String>,
+    /// Signing key for `private_key_jwt`/`client_secret_jwt` client authentication: a PEM-encoded
+    /// private key (or, for `client_secret_jwt`, a plain HMAC secret), given inline or as a path
+    /// to a file. Mutually exclusive with `--oidc-client-secret`
+    #[arg(
+        id = "oidc_client_assertion_key",
+        long = "oidc-client-assertion-key",
+        env = "OIDC_PROVIDER_CLIENT_ASSERTION_KEY",
+        conflicts_with = "oidc_client_secret"
+    )]
+    pub client_assertion_key: Option<String>,
+    /// `kid` header to include on client-assertion JWTs
+    #[arg(
+        id = "oidc_client_assertion_kid",
+        long = "oidc-client-assertion-kid",
+        env = "OIDC_PROVIDER_CLIENT_ASSERTION_KID",
+        requires = "oidc_client_assertion_key"
+    )]
+    pub client_assertion_kid: Option<String>,
+    /// Algorithm used to sign client-assertion JWTs
+    #[arg(
+        id = "oidc_client_assertion_alg",
+        long = "oidc-client-assertion-alg",
+        env = "OIDC_PROVIDER_CLIENT_ASSERTION_ALG",
+        default_value = "rs256",
+        requires = "oidc_client_assertion_key"
+    )]
+    pub client_assertion_alg: ClientAssertionAlg,
+    /// Proactively refresh the token in the background, ahead of expiry, instead of only
+    /// refreshing lazily when a caller asks for a token that's about to expire
+    #[arg(
+        id = "oidc_background_refresh",
+        long = "oidc-background-refresh",
+        env = "OIDC_PROVIDER_BACKGROUND_REFRESH",
+        default_value = "false"
+    )]
+    pub background_refresh: bool,
+}
+
+/// OAuth2 grant type used for the initial token exchange
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum GrantType {
+    #[default]
+    ClientCredentials,
+    Password,
+    RefreshToken,
+    AuthorizationCode,
+}
+
+/// Algorithm used to sign RFC 7523 client-assertion JWTs for `private_key_jwt` (`RS256`/`ES256`)
+/// or `client_secret_jwt` (`HS256`) client authentication
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ClientAssertionAlg {
+    #[default]
+    #[value(name = "rs256")]
+    Rs256,
+    #[value(name = "es256")]
+    Es256,
+    #[value(name = "hs256")]
+    Hs256,
+}
+
+impl ClientAssertionAlg {
+    fn to_jsonwebtoken(self) -> jsonwebtoken::Algorithm {
+        match self {
+            ClientAssertionAlg::Rs256 => jsonwebtoken::Algorithm::RS256,
+            ClientAssertionAlg::Es256 => jsonwebtoken::Algorithm::ES256,
+            ClientAssertionAlg::Hs256 => jsonwebtoken::Algorithm::HS256,
+        }
+    }
 }
 
 impl OpenIdTokenProviderConfigArguments {
@@ -68,6 +206,18 @@ impl OpenIdTokenProviderConfigArguments {
             refresh_before: Duration::from_secs(30).into(),
             tls_insecure: false,
             scopes: None,
+            initial_access_token: None,
+            client_name: "trustify".to_string(),
+            grant_type: GrantType::ClientCredentials,
+            username: None,
+            password: None,
+            refresh_token: None,
+            authorization_code: None,
+            redirect_uri: None,
+            client_assertion_key: None,
+            client_assertion_kid: None,
+            client_assertion_alg: ClientAssertionAlg::Rs256,
+            background_refresh: false,
         }
     }
 }
@@ -92,14 +242,63 @@ impl OpenIdTokenProviderConfigArguments {
 
 #[derive(Clone, Debug, PartialEq, Eq, clap::Args)]
 pub struct OpenIdTokenProviderConfig {
-    pub client_id: String,
-    pub client_secret: String,
+    /// Pre-provisioned client ID. Mutually exclusive with `initial_access_token`, which triggers
+    /// Dynamic Client Registration instead.
+    pub client_id: Option<String>,
+    /// Pre-provisioned client secret. Mutually exclusive with `initial_access_token`.
+    pub client_secret: Option<String>,
     pub issuer_url: String,
     pub refresh_before: humantime::Duration,
     pub tls_insecure: bool,
     /// Custom scopes to request when obtaining tokens (space-separated)
     #[arg(long = "oidc-scopes", env = "OIDC_PROVIDER_SCOPES")]
     pub scopes: Option<String>,
+    /// Initial access token used to register a new OAuth2 client via RFC 7591 Dynamic Client
+    /// Registration in `with_config`, instead of using `client_id`/`client_secret` directly
+    #[arg(long = "oidc-registration-token", env = "OIDC_PROVIDER_REGISTRATION_TOKEN")]
+    pub initial_access_token: Option<String>,
+    /// `client_name` sent with a Dynamic Client Registration request
+    #[arg(long = "oidc-client-name", env = "OIDC_PROVIDER_CLIENT_NAME", default_value = "trustify")]
+    pub client_name: String,
+    /// OAuth2 grant type to use for the initial token exchange
+    #[arg(long = "oidc-grant-type", env = "OIDC_PROVIDER_GRANT_TYPE", default_value = "client-credentials")]
+    pub grant_type: GrantType,
+    /// Username for the `password` grant
+    #[arg(long = "oidc-username", env = "OIDC_PROVIDER_USERNAME")]
+    pub username: Option<String>,
+    /// Password for the `password` grant
+    #[arg(long = "oidc-password", env = "OIDC_PROVIDER_PASSWORD")]
+    pub password: Option<String>,
+    /// Seed refresh token for the `refresh-token` grant
+    #[arg(long = "oidc-refresh-token", env = "OIDC_PROVIDER_REFRESH_TOKEN")]
+    pub refresh_token: Option<String>,
+    /// Pre-obtained authorization code for the `authorization-code` grant
+    #[arg(long = "oidc-authorization-code", env = "OIDC_PROVIDER_AUTHORIZATION_CODE")]
+    pub authorization_code: Option<String>,
+    /// Redirect URI the authorization code was issued for
+    #[arg(long = "oidc-redirect-uri", env = "OIDC_PROVIDER_REDIRECT_URI")]
+    pub redirect_uri: Option<String>,
+    /// Signing key for `private_key_jwt`/`client_secret_jwt` client authentication, inline or a
+    /// path to a file. Mutually exclusive with `client_secret`
+    #[arg(long = "oidc-client-assertion-key", env = "OIDC_PROVIDER_CLIENT_ASSERTION_KEY")]
+    pub client_assertion_key: Option<String>,
+    /// `kid` header to include on client-assertion JWTs
+    #[arg(long = "oidc-client-assertion-kid", env = "OIDC_PROVIDER_CLIENT_ASSERTION_KID")]
+    pub client_assertion_kid: Option<String>,
+    /// Algorithm used to sign client-assertion JWTs
+    #[arg(
+        long = "oidc-client-assertion-alg",
+        env = "OIDC_PROVIDER_CLIENT_ASSERTION_ALG",
+        default_value = "rs256"
+    )]
+    pub client_assertion_alg: ClientAssertionAlg,
+    /// Proactively refresh the token in the background, ahead of expiry
+    #[arg(
+        long = "oidc-background-refresh",
+        env = "OIDC_PROVIDER_BACKGROUND_REFRESH",
+        default_value = "false"
+    )]
+    pub background_refresh: bool,
 }
 
 impl OpenIdTokenProviderConfig {
@@ -129,14 +328,38 @@ impl OpenIdTokenProviderConfig {
         Ok(())
     }
 
+    /// Validate that client authentication is configured unambiguously: a `client_secret` and a
+    /// `client_assertion_key` can't both be set, since each selects a different
+    /// `token_endpoint_auth_method`.
+    pub fn validate_client_auth(&self) -> Result<(), String> {
+        if self.client_secret.is_some() && self.client_assertion_key.is_some() {
+            return Err(
+                "client_secret and client_assertion_key are mutually exclusive".to_string(),
+            );
+        }
+        Ok(())
+    }
+
     pub fn devmode() -> Self {
         Self {
             issuer_url: devmode::issuer_url(),
-            client_id: devmode::SERVICE_CLIENT_ID.to_string(),
-            client_secret: devmode::SSO_CLIENT_SECRET.to_string(),
+            client_id: Some(devmode::SERVICE_CLIENT_ID.to_string()),
+            client_secret: Some(devmode::SSO_CLIENT_SECRET.to_string()),
             refresh_before: Duration::from_secs(30).into(),
             tls_insecure: false,
             scopes: None,
+            initial_access_token: None,
+            client_name: "trustify".to_string(),
+            grant_type: GrantType::ClientCredentials,
+            username: None,
+            password: None,
+            refresh_token: None,
+            authorization_code: None,
+            redirect_uri: None,
+            client_assertion_key: None,
+            client_assertion_kid: None,
+            client_assertion_alg: ClientAssertionAlg::Rs256,
+            background_refresh: false,
         }
     }
 
@@ -158,23 +381,35 @@ impl OpenIdTokenProviderConfig {
     }
 
     pub fn from_args(arguments: OpenIdTokenProviderConfigArguments) -> Option<Self> {
-        match (
-            arguments.client_id,
-            arguments.client_secret,
-            arguments.issuer_url,
-        ) {
-            (Some(client_id), Some(client_secret), Some(issuer_url)) => {
-                Some(OpenIdTokenProviderConfig {
-                    client_id,
-                    client_secret,
-                    issuer_url,
-                    refresh_before: arguments.refresh_before,
-                    tls_insecure: arguments.tls_insecure,
-                    scopes: Self::parse_scopes(arguments.scopes),
-                })
-            }
-            _ => None,
+        let issuer_url = arguments.issuer_url?;
+
+        // Either a pre-provisioned client_id/client_secret pair, or an initial access token to
+        // register a new client via Dynamic Client Registration, is required.
+        let has_static_credentials = arguments.client_id.is_some() && arguments.client_secret.is_some();
+        if !has_static_credentials && arguments.initial_access_token.is_none() {
+            return None;
         }
+
+        Some(OpenIdTokenProviderConfig {
+            client_id: arguments.client_id,
+            client_secret: arguments.client_secret,
+            issuer_url,
+            refresh_before: arguments.refresh_before,
+            tls_insecure: arguments.tls_insecure,
+            scopes: Self::parse_scopes(arguments.scopes),
+            initial_access_token: arguments.initial_access_token,
+            client_name: arguments.client_name,
+            grant_type: arguments.grant_type,
+            username: arguments.username,
+            password: arguments.password,
+            refresh_token: arguments.refresh_token,
+            authorization_code: arguments.authorization_code,
+            redirect_uri: arguments.redirect_uri,
+            client_assertion_key: arguments.client_assertion_key,
+            client_assertion_kid: arguments.client_assertion_kid,
+            client_assertion_alg: arguments.client_assertion_alg,
+            background_refresh: arguments.background_refresh,
+        })
     }
 }
 
@@ -184,6 +419,427 @@ impl From<OpenIdTokenProviderConfigArguments> for Option<OpenIdTokenProviderConf
     }
 }
 
+/// A `registration_endpoint` in an OIDC discovery document, the only field Dynamic Client
+/// Registration needs from it
+#[derive(Debug, serde::Deserialize)]
+struct OidcDiscoveryDocument {
+    registration_endpoint: Option<String>,
+}
+
+/// RFC 7591 client registration request body
+#[derive(Debug, serde::Serialize)]
+struct ClientRegistrationRequest<'a> {
+    client_name: &'a str,
+    grant_types: Vec<&'a str>,
+    token_endpoint_auth_method: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<&'a str>,
+}
+
+/// Result of registering a new OAuth2 client via RFC 7591 Dynamic Client Registration
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ClientRegistration {
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    /// Token authorizing later reads/updates of this registration, per RFC 7592
+    pub registration_access_token: Option<String>,
+    /// URI for later reads/updates of this registration, per RFC 7592
+    pub registration_client_uri: Option<String>,
+}
+
+/// Register a new `client_credentials` OAuth2 client with `issuer_url`'s `registration_endpoint`
+/// (discovered from its `.well-known/openid-configuration`), authenticating the registration
+/// request with `initial_access_token`
+async fn register_client(
+    http_client: &reqwest::Client,
+    issuer_url: &str,
+    initial_access_token: &str,
+    client_name: &str,
+    scope: Option<&str>,
+) -> anyhow::Result<ClientRegistration> {
+    let discovery_url = format!(
+        "{}/.well-known/openid-configuration",
+        issuer_url.trim_end_matches('/')
+    );
+
+    let discovery: OidcDiscoveryDocument = http_client
+        .get(discovery_url)
+        .send()
+        .await
+        .context("fetch OIDC discovery document")?
+        .error_for_status()
+        .context("OIDC discovery document request failed")?
+        .json()
+        .await
+        .context("parse OIDC discovery document")?;
+
+    let registration_endpoint = discovery
+        .registration_endpoint
+        .context("issuer does not advertise a registration_endpoint")?;
+
+    let request = ClientRegistrationRequest {
+        client_name,
+        grant_types: vec!["client_credentials"],
+        token_endpoint_auth_method: "client_secret_basic",
+        scope,
+    };
+
+    http_client
+        .post(registration_endpoint)
+        .bearer_auth(initial_access_token)
+        .json(&request)
+        .send()
+        .await
+        .context("send dynamic client registration request")?
+        .error_for_status()
+        .context("dynamic client registration failed")?
+        .json::<ClientRegistration>()
+        .await
+        .context("parse dynamic client registration response")
+}
+
+/// Where `OpenIdTokenProvider` persists its current token between process invocations, keyed by
+/// `issuer_url` + `client_id`, so the CLI can reuse a session instead of re-authenticating on
+/// every invocation.
+pub mod store {
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    /// A token as persisted across restarts.
+    #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+    pub struct StoredToken {
+        pub access_token: String,
+        pub refresh_token: Option<String>,
+        /// Absolute expiry as unix seconds, or `None` if the IdP didn't report a TTL
+        pub expires_at: Option<u64>,
+    }
+
+    /// A place `OpenIdTokenProvider` can load/save/clear its current token, so callers aren't
+    /// tied to the file-backed default.
+    pub trait TokenStore: Send + Sync {
+        fn load(&self) -> Option<StoredToken>;
+        fn save(&self, token: &StoredToken);
+        fn clear(&self);
+    }
+
+    /// A [`TokenStore`] that never persists anything; the default until `with_config` installs
+    /// a file-backed one.
+    pub(super) struct NullTokenStore;
+
+    impl TokenStore for NullTokenStore {
+        fn load(&self) -> Option<StoredToken> {
+            None
+        }
+
+        fn save(&self, _token: &StoredToken) {}
+
+        fn clear(&self) {}
+    }
+
+    /// Default [`TokenStore`], writing to `$XDG_CACHE_HOME/trustify/token-<hash>.json` (or the
+    /// platform equivalent), restricted to `0600` so the access/refresh tokens aren't
+    /// world-readable.
+    pub struct FileTokenStore {
+        path: Option<PathBuf>,
+    }
+
+    impl FileTokenStore {
+        pub fn new(issuer_url: &str, client_id: &str) -> Self {
+            Self {
+                path: Self::cache_path(issuer_url, client_id),
+            }
+        }
+
+        fn cache_path(issuer_url: &str, client_id: &str) -> Option<PathBuf> {
+            use sha2::{Digest, Sha256};
+
+            let mut hasher = Sha256::new();
+            hasher.update(issuer_url.as_bytes());
+            hasher.update(b"\0");
+            hasher.update(client_id.as_bytes());
+            let hash = format!("{:x}", hasher.finalize());
+
+            Some(
+                dirs::cache_dir()?
+                    .join("trustify")
+                    .join(format!("token-{hash}.json")),
+            )
+        }
+    }
+
+    impl TokenStore for FileTokenStore {
+        fn load(&self) -> Option<StoredToken> {
+            let path = self.path.as_ref()?;
+            let data = fs::read(path).ok()?;
+            let token: StoredToken = serde_json::from_slice(&data).ok()?;
+
+            if let Some(expires_at) = token.expires_at {
+                let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+                if now >= expires_at {
+                    return None;
+                }
+            }
+
+            Some(token)
+        }
+
+        fn save(&self, token: &StoredToken) {
+            let Some(path) = &self.path else { return };
+            let Some(parent) = path.parent() else { return };
+            if fs::create_dir_all(parent).is_err() {
+                return;
+            }
+
+            let Ok(data) = serde_json::to_vec(token) else { return };
+
+            write_private(path, &data);
+        }
+
+        fn clear(&self) {
+            if let Some(path) = &self.path {
+                let _ = fs::remove_file(path);
+            }
+        }
+    }
+
+    /// Write `data` to `path`, creating the file with `0600` permissions from the outset on
+    /// unix (instead of `write` then `chmod`, which leaves a window where the file has default,
+    /// often group/world-readable, permissions). Writes to a sibling temp file first and renames
+    /// it into place, so a reader can never observe a partially written file at `path` either.
+    fn write_private(path: &std::path::Path, data: &[u8]) {
+        let Some(parent) = path.parent() else { return };
+        let tmp_path = parent.join(format!(".{}.tmp", std::process::id()));
+
+        #[cfg(unix)]
+        let opened = {
+            use std::os::unix::fs::OpenOptionsExt;
+            fs::OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .mode(0o600)
+                .open(&tmp_path)
+        };
+        #[cfg(not(unix))]
+        let opened = fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&tmp_path);
+
+        let Ok(mut file) = opened else { return };
+
+        use std::io::Write;
+        if file.write_all(data).is_err() {
+            let _ = fs::remove_file(&tmp_path);
+            return;
+        }
+        drop(file);
+
+        if fs::rename(&tmp_path, path).is_err() {
+            let _ = fs::remove_file(&tmp_path);
+        }
+    }
+}
+
+/// Convert a live `Bearer` into its on-disk form, turning its relative TTL into an absolute unix
+/// expiry so it survives a process restart.
+fn bearer_to_stored(bearer: &openid::Bearer) -> store::StoredToken {
+    let expires_at = bearer.expires_in.map(|ttl| {
+        (SystemTime::now() + ttl)
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    });
+
+    store::StoredToken {
+        access_token: bearer.access_token.clone(),
+        refresh_token: bearer.refresh_token.clone(),
+        expires_at,
+    }
+}
+
+/// Reconstruct a `Bearer` from its on-disk form, converting the persisted absolute unix expiry
+/// back into a relative TTL.
+fn stored_to_bearer(stored: store::StoredToken) -> openid::Bearer {
+    let expires_in = stored.expires_at.map(|expires_at| {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        Duration::from_secs(expires_at.saturating_sub(now))
+    });
+
+    openid::Bearer {
+        access_token: stored.access_token,
+        refresh_token: stored.refresh_token,
+        id_token: None,
+        expires_in,
+        scope: None,
+        token_type: Some("bearer".to_string()),
+    }
+}
+
+/// A ready-to-sign RFC 7523 client assertion, used in place of sending `client_secret` directly
+/// when `private_key_jwt`/`client_secret_jwt` client authentication is configured.
+struct ClientAssertion {
+    client_id: String,
+    token_endpoint: String,
+    kid: Option<String>,
+    alg: jsonwebtoken::Algorithm,
+    key: jsonwebtoken::EncodingKey,
+}
+
+/// RFC 7523 client-assertion JWT claims
+#[derive(serde::Serialize)]
+struct ClientAssertionClaims<'a> {
+    iss: &'a str,
+    sub: &'a str,
+    aud: &'a str,
+    jti: String,
+    exp: i64,
+    iat: i64,
+}
+
+impl ClientAssertion {
+    /// Load the signing key for `client_assertion_key`, treating it as a path if a file exists
+    /// there, or as inline PEM/secret text otherwise.
+    fn load(
+        client_id: String,
+        token_endpoint: String,
+        raw_key: &str,
+        kid: Option<String>,
+        alg: ClientAssertionAlg,
+    ) -> anyhow::Result<Self> {
+        let key_bytes = match std::fs::read(raw_key) {
+            Ok(bytes) => bytes,
+            Err(_) => raw_key.as_bytes().to_vec(),
+        };
+
+        let alg = alg.to_jsonwebtoken();
+        let key = match alg {
+            jsonwebtoken::Algorithm::HS256 => jsonwebtoken::EncodingKey::from_secret(&key_bytes),
+            jsonwebtoken::Algorithm::ES256 => jsonwebtoken::EncodingKey::from_ec_pem(&key_bytes)
+                .context("Parse EC private key for client assertion signing")?,
+            _ => jsonwebtoken::EncodingKey::from_rsa_pem(&key_bytes)
+                .context("Parse RSA private key for client assertion signing")?,
+        };
+
+        Ok(Self {
+            client_id,
+            token_endpoint,
+            kid,
+            alg,
+            key,
+        })
+    }
+
+    /// Sign a fresh, short-lived assertion JWT. Only fails if the key validated in
+    /// [`Self::load`] can no longer encode, which would indicate an internal bug rather than a
+    /// recoverable configuration error.
+    fn sign(&self) -> String {
+        let mut header = jsonwebtoken::Header::new(self.alg);
+        header.kid = self.kid.clone();
+
+        let now = chrono::Utc::now().timestamp();
+        let claims = ClientAssertionClaims {
+            iss: &self.client_id,
+            sub: &self.client_id,
+            aud: &self.token_endpoint,
+            jti: uuid::Uuid::new_v4().to_string(),
+            exp: now + 60,
+            iat: now,
+        };
+
+        jsonwebtoken::encode(&header, &claims, &self.key)
+            .expect("client assertion key was already validated in ClientAssertion::load")
+    }
+}
+
+/// Diagnostic state of the opt-in background refresher (see `--oidc-background-refresh`).
+#[derive(Clone, Debug, PartialEq)]
+pub enum RefreshState {
+    /// Background refresh isn't configured; tokens are only refreshed lazily, on demand
+    Disabled,
+    /// Waiting until the next scheduled refresh
+    Idle,
+    /// A refresh is currently in flight
+    Refreshing,
+    /// The last `attempt` refresh(es) failed; retrying with backoff while keeping the
+    /// last-known-good token available
+    Backoff { attempt: u32 },
+}
+
+/// Minimum poll interval for the background refresher, so a tiny `refresh_before` doesn't spin
+const BACKGROUND_REFRESH_MIN_POLL: Duration = Duration::from_secs(1);
+/// Base delay for the first backoff retry after a failed background refresh
+const BACKGROUND_REFRESH_BASE_BACKOFF_MS: u64 = 1_000;
+/// Cap on backoff between background refresh retries
+const BACKGROUND_REFRESH_MAX_BACKOFF_MS: u64 = 300_000;
+
+/// Exponential backoff with full jitter: `random(0, min(cap, base * 2^attempt))`, mirroring the
+/// retry strategy `ApiClient` uses for its own request retries.
+fn background_refresh_backoff(attempt: u32) -> Duration {
+    let capped = BACKGROUND_REFRESH_BASE_BACKOFF_MS
+        .saturating_mul(1u64 << attempt.min(16))
+        .min(BACKGROUND_REFRESH_MAX_BACKOFF_MS);
+    let jittered = rand::rng().random_range(0..=capped);
+    Duration::from_millis(jittered)
+}
+
+/// Aborts the background refresh task when the last reference to it is dropped, so it doesn't
+/// outlive every clone of the `OpenIdTokenProvider` it belongs to.
+struct AbortOnDrop(tokio::task::JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// The grant-specific data `initial_token` needs to perform the initial token exchange. Carries
+/// the same cases as [`GrantType`] plus whatever credentials that grant requires.
+#[derive(Clone, Debug)]
+enum Grant {
+    ClientCredentials,
+    Password { username: String, password: String },
+    RefreshToken { refresh_token: String },
+    AuthorizationCode { code: String },
+}
+
+impl Grant {
+    /// Build the grant from a config's `grant_type` and its companion fields, failing if a
+    /// required companion field is missing for the selected grant type.
+    fn from_config(config: &OpenIdTokenProviderConfig) -> anyhow::Result<Self> {
+        Ok(match config.grant_type {
+            GrantType::ClientCredentials => Grant::ClientCredentials,
+            GrantType::Password => Grant::Password {
+                username: config
+                    .username
+                    .clone()
+                    .context("--oidc-username is required for the password grant")?,
+                password: config
+                    .password
+                    .clone()
+                    .context("--oidc-password is required for the password grant")?,
+            },
+            GrantType::RefreshToken => Grant::RefreshToken {
+                refresh_token: config
+                    .refresh_token
+                    .clone()
+                    .context("--oidc-refresh-token is required for the refresh-token grant")?,
+            },
+            GrantType::AuthorizationCode => Grant::AuthorizationCode {
+                code: config
+                    .authorization_code
+                    .clone()
+                    .context("--oidc-authorization-code is required for the authorization-code grant")?,
+            },
+        })
+    }
+}
+
 /// A provider which provides access tokens for clients.
 #[derive(Clone)]
 pub struct OpenIdTokenProvider {
@@ -191,6 +847,33 @@ pub struct OpenIdTokenProvider {
     current_token: Arc<RwLock<Option<openid::TemporalBearerGuard>>>,
     refresh_before: chrono::Duration,
     scopes: Option<Box<str>>,
+    /// Set when this provider's client was created via Dynamic Client Registration, so the
+    /// registration can later be rotated or deleted via `registration_client_uri`.
+    registration: Option<ClientRegistration>,
+    /// Grant used for the initial token exchange; defaults to `client_credentials`
+    grant: Grant,
+    /// Where the current token is persisted across process restarts; a no-op until
+    /// `with_config` installs a file-backed store
+    store: Arc<dyn TokenStore>,
+    /// Set when `private_key_jwt`/`client_secret_jwt` client authentication is configured,
+    /// instead of sending `client_secret` directly
+    client_assertion: Option<Arc<ClientAssertion>>,
+    /// Diagnostic state of the background refresher, if one is running
+    refresh_state: Arc<RwLock<RefreshState>>,
+    /// Keeps the background refresh task alive for as long as any clone of this provider
+    /// exists; `None` unless `--oidc-background-refresh` is set
+    background_refresh: Option<Arc<AbortOnDrop>>,
+    /// Caches `introspect_token` results keyed by a hash of the introspected token, bounded by
+    /// the token's own `exp` claim, so repeated introspection of the same still-valid token
+    /// doesn't round-trip to the issuer's introspection endpoint every time.
+    introspection_cache: Arc<Mutex<HashMap<u64, CachedIntrospection>>>,
+}
+
+/// A cached `introspect_token` result, alongside the absolute unix time it stops being valid.
+#[derive(Clone)]
+struct CachedIntrospection {
+    response: IntrospectionResponse,
+    expires_at: u64,
 }
 
 impl Debug for OpenIdTokenProvider {
@@ -213,37 +896,174 @@ impl OpenIdTokenProvider {
             current_token: Arc::new(RwLock::new(None)),
             refresh_before,
             scopes: scopes.map(|s| s.into_boxed_str()),
+            registration: None,
+            grant: Grant::ClientCredentials,
+            store: Arc::new(store::NullTokenStore),
+            client_assertion: None,
+            refresh_state: Arc::new(RwLock::new(RefreshState::Disabled)),
+            background_refresh: None,
+            introspection_cache: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
+    /// The client registration returned by Dynamic Client Registration, if this provider's
+    /// client was created that way instead of from a pre-provisioned `client_id`/`client_secret`.
+    pub fn registration(&self) -> Option<&ClientRegistration> {
+        self.registration.as_ref()
+    }
+
+    /// Current state of the background refresher, for diagnostics/health checks.
+    pub async fn refresh_state(&self) -> RefreshState {
+        self.refresh_state.read().await.clone()
+    }
+
+    /// Start the opt-in background refresher: wakes up periodically, refreshes the token under
+    /// the same write-lock `fetch_fresh_token` uses on the hot path once it's within
+    /// `refresh_before` of expiry, and retries with jittered, capped exponential backoff on
+    /// failure while leaving the last-known-good token in place for `provide_token` to keep
+    /// serving. The spawned task is aborted once every clone of this provider is dropped.
+    async fn start_background_refresh(&mut self) {
+        let provider = self.clone();
+        let refresh_state = self.refresh_state.clone();
+
+        let poll_interval = provider
+            .refresh_before
+            .to_std()
+            .unwrap_or(Duration::from_secs(30))
+            .checked_div(2)
+            .unwrap_or(BACKGROUND_REFRESH_MIN_POLL)
+            .max(BACKGROUND_REFRESH_MIN_POLL);
+
+        *refresh_state.write().await = RefreshState::Idle;
+
+        let handle = tokio::spawn(async move {
+            let mut attempt: u32 = 0;
+
+            loop {
+                tokio::time::sleep(poll_interval).await;
+
+                let needs_refresh = match provider.current_token.read().await.deref() {
+                    Some(token) => token.expires_before(provider.refresh_before),
+                    None => true,
+                };
+
+                if !needs_refresh {
+                    continue;
+                }
+
+                *refresh_state.write().await = RefreshState::Refreshing;
+
+                match provider.fetch_fresh_token().await {
+                    Ok(_) => {
+                        attempt = 0;
+                        *refresh_state.write().await = RefreshState::Idle;
+                    }
+                    Err(e) => {
+                        attempt += 1;
+                        log::warn!("Background token refresh failed (attempt {attempt}): {e}");
+                        *refresh_state.write().await = RefreshState::Backoff { attempt };
+                        tokio::time::sleep(background_refresh_backoff(attempt)).await;
+                    }
+                }
+            }
+        });
+
+        self.background_refresh = Some(Arc::new(AbortOnDrop(handle)));
+    }
+
     pub async fn with_config(config: OpenIdTokenProviderConfig) -> anyhow::Result<Self> {
         // Validate scopes before proceeding
         config.validate_scopes().map_err(|e| anyhow::anyhow!("Invalid scopes: {}", e))?;
+        config
+            .validate_client_auth()
+            .map_err(|e| anyhow::anyhow!("Invalid client authentication: {}", e))?;
 
         let issuer = Url::parse(&config.issuer_url).context("Parse issuer URL")?;
-        let mut client = reqwest::ClientBuilder::new();
+        let mut builder = reqwest::ClientBuilder::new();
 
         if config.tls_insecure {
             log::warn!("Using insecure TLS when contacting the OIDC issuer");
-            client = client
+            builder = builder
                 .danger_accept_invalid_certs(true)
                 .danger_accept_invalid_hostnames(true);
         }
 
+        let http_client = builder.build()?;
+
+        let grant = Grant::from_config(&config)?;
+
+        let (client_id, client_secret, registration) =
+            match (&config.client_id, &config.client_secret) {
+                (Some(client_id), Some(client_secret)) => {
+                    (client_id.clone(), Some(client_secret.clone()), None)
+                }
+                _ => {
+                    let initial_access_token = config
+                        .initial_access_token
+                        .as_deref()
+                        .context("either client_id/client_secret or an initial_access_token is required")?;
+
+                    let registration = register_client(
+                        &http_client,
+                        &config.issuer_url,
+                        initial_access_token,
+                        &config.client_name,
+                        config.scopes(),
+                    )
+                    .await
+                    .context("Dynamic Client Registration")?;
+
+                    let client_secret = registration.client_secret.clone();
+                    (registration.client_id.clone(), client_secret, Some(registration))
+                }
+            };
+
         let client = openid::Client::discover_with_client(
-            client.build()?,
-            config.client_id,
-            config.client_secret,
+            http_client,
+            client_id,
+            client_secret,
             None,
             issuer,
         )
         .await
         .context("Discover OIDC client")?;
-        Ok(Self::new(
+
+        let store = store::FileTokenStore::new(&config.issuer_url, &client.client_id);
+
+        let client_assertion = match &config.client_assertion_key {
+            Some(raw_key) => Some(Arc::new(ClientAssertion::load(
+                client.client_id.clone(),
+                client.config().token_endpoint.clone(),
+                raw_key,
+                config.client_assertion_kid.clone(),
+                config.client_assertion_alg,
+            )?)),
+            None => None,
+        };
+
+        let mut provider = Self::new(
             client,
             chrono::Duration::from_std(config.refresh_before.into())?,
             config.scopes,
-        ))
+        );
+        provider.registration = registration;
+        provider.grant = grant;
+
+        // Reuse a still-valid token from a previous invocation instead of re-authenticating
+        // against the issuer on every CLI command.
+        if let Some(stored) = store.load() {
+            log::debug!("Seeding token from store");
+            *provider.current_token.write().await = Some(stored_to_bearer(stored).into());
+        }
+
+        provider.store = Arc::new(store);
+        provider.client_assertion = client_assertion;
+
+        if config.background_refresh {
+            provider.start_background_refresh().await;
+        }
+
+        Ok(provider)
     }
 
     /// return a fresh token, this may be an existing (non-expired) token
@@ -279,17 +1099,30 @@ impl OpenIdTokenProvider {
         // we hold the write-lock now, and can perform the refresh operation
 
         let next_token = match lock.take() {
-            // if we don't have any token, fetch an initial one
-            None => {
-                log::debug!("Fetching initial token... ");
-                self.initial_token().await?
-            }
+            // if we don't have any token, consult the store before minting an initial one
+            None => match self.store.load() {
+                Some(stored) => {
+                    log::debug!("Restoring token from store");
+                    stored_to_bearer(stored).into()
+                }
+                None => {
+                    log::debug!("Fetching initial token... ");
+                    self.initial_token().await?
+                }
+            },
             // if we have an expired one, refresh it
             Some(current_token) => {
                 log::debug!("Refreshing token ... ");
-                match current_token.as_ref().refresh_token.is_some() {
-                    true => self.client.refresh_token(current_token, None).await?.into(),
-                    false => self.initial_token().await?,
+                match (current_token.as_ref().refresh_token.clone(), &self.client_assertion) {
+                    (Some(refresh_token), Some(assertion)) => {
+                        let form = vec![
+                            ("grant_type", "refresh_token".to_string()),
+                            ("refresh_token", refresh_token),
+                        ];
+                        self.token_request_with_assertion(assertion, form).await?
+                    }
+                    (Some(_), None) => self.client.refresh_token(current_token, None).await?.into(),
+                    (None, _) => self.initial_token().await?,
                 }
             }
         };
@@ -297,6 +1130,7 @@ impl OpenIdTokenProvider {
         log::debug!("Next token: {:?}", next_token.as_ref());
 
         let result = next_token.as_ref().clone();
+        self.store.save(&bearer_to_stored(&result));
         lock.replace(next_token);
 
         // done
@@ -312,12 +1146,202 @@ impl OpenIdTokenProvider {
             log::debug!("Requesting token without specific scopes");
         }
 
-        Ok(self
+        match &self.grant {
+            Grant::ClientCredentials => match &self.client_assertion {
+                Some(assertion) => {
+                    let mut form = vec![("grant_type", "client_credentials".to_string())];
+                    if let Some(scopes) = scopes {
+                        form.push(("scope", scopes.to_string()));
+                    }
+                    self.token_request_with_assertion(assertion, form).await
+                }
+                None => Ok(self
+                    .client
+                    .request_token_using_client_credentials(scopes)
+                    .await?
+                    .into()),
+            },
+
+            Grant::Password { username, password } => match &self.client_assertion {
+                Some(assertion) => {
+                    let mut form = vec![
+                        ("grant_type", "password".to_string()),
+                        ("username", username.clone()),
+                        ("password", password.clone()),
+                    ];
+                    if let Some(scopes) = scopes {
+                        form.push(("scope", scopes.to_string()));
+                    }
+                    self.token_request_with_assertion(assertion, form).await
+                }
+                None => Ok(self
+                    .client
+                    .request_token_using_password_credentials(username, password, scopes)
+                    .await?
+                    .into()),
+            },
+
+            Grant::AuthorizationCode { code } => match &self.client_assertion {
+                Some(assertion) => {
+                    let form = vec![
+                        ("grant_type", "authorization_code".to_string()),
+                        ("code", code.clone()),
+                    ];
+                    self.token_request_with_assertion(assertion, form).await
+                }
+                None => Ok(self.client.request_token(code).await?.into()),
+            },
+
+            // The refresh-token grant has no initial exchange: seed a bearer carrying only the
+            // configured refresh token and hand it straight to the refresh endpoint.
+            Grant::RefreshToken { refresh_token } => match &self.client_assertion {
+                Some(assertion) => {
+                    let form = vec![
+                        ("grant_type", "refresh_token".to_string()),
+                        ("refresh_token", refresh_token.clone()),
+                    ];
+                    self.token_request_with_assertion(assertion, form).await
+                }
+                None => {
+                    let seed: openid::TemporalBearerGuard = openid::Bearer {
+                        access_token: String::new(),
+                        refresh_token: Some(refresh_token.clone()),
+                        id_token: None,
+                        expires_in: None,
+                        scope: scopes.map(str::to_string),
+                        token_type: Some("bearer".to_string()),
+                    }
+                    .into();
+
+                    Ok(self.client.refresh_token(seed, None).await?.into())
+                }
+            },
+        }
+    }
+
+    /// Exchange `form` for a token at the issuer's token endpoint, attaching a signed RFC 7523
+    /// client assertion instead of sending `client_secret`. Used for both the initial exchange
+    /// and refreshes when `private_key_jwt`/`client_secret_jwt` client authentication is
+    /// configured, since neither is supported by the underlying `openid` client.
+    async fn token_request_with_assertion(
+        &self,
+        assertion: &ClientAssertion,
+        mut form: Vec<(&str, String)>,
+    ) -> Result<openid::TemporalBearerGuard, openid::error::Error> {
+        form.push(("client_id", assertion.client_id.clone()));
+        form.push((
+            "client_assertion_type",
+            "urn:ietf:params:oauth:client-assertion-type:jwt-bearer".to_string(),
+        ));
+        form.push(("client_assertion", assertion.sign()));
+
+        let bearer = self
             .client
-            .request_token_using_client_credentials(scopes)
+            .http_client
+            .post(&assertion.token_endpoint)
+            .form(&form)
+            .send()
             .await?
-            .into())
+            .error_for_status()?
+            .json::<openid::Bearer>()
+            .await?;
+
+        Ok(bearer.into())
     }
+
+    /// Introspect `token` against the issuer's RFC 7662 `introspection_endpoint`, authenticating
+    /// with this provider's own client credentials. Returns an error if the issuer's discovery
+    /// document doesn't advertise an introspection endpoint, or
+    /// [`IntrospectionError::Inactive`] if the issuer reports the token as no longer active.
+    ///
+    /// Results are cached by a hash of `token`, bounded by the token's own `exp` claim (or not
+    /// cached at all if the response omits `exp`), so repeatedly introspecting the same
+    /// still-valid token doesn't round-trip to the issuer every time.
+    pub async fn introspect_token(&self, token: &str) -> anyhow::Result<IntrospectionResponse> {
+        let cache_key = introspection_cache_key(token);
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        if let Some(cached) = self.introspection_cache.lock().unwrap().get(&cache_key) {
+            if cached.expires_at > now {
+                return Ok(cached.response.clone());
+            }
+        }
+
+        let endpoint = self
+            .client
+            .config()
+            .introspection_endpoint
+            .clone()
+            .context("issuer does not advertise an introspection_endpoint")?;
+
+        let response = self
+            .client
+            .http_client
+            .post(endpoint)
+            .basic_auth(&self.client.client_id, self.client.client_secret.as_deref())
+            .form(&[("token", token), ("token_type_hint", "access_token")])
+            .send()
+            .await
+            .context("send introspection request")?
+            .error_for_status()
+            .context("introspection endpoint returned an error status")?
+            .json::<IntrospectionResponse>()
+            .await
+            .context("parse introspection response")?;
+
+        if !response.active {
+            return Err(IntrospectionError::Inactive.into());
+        }
+
+        if let Some(exp) = response.exp.and_then(|exp| u64::try_from(exp).ok()) {
+            self.introspection_cache.lock().unwrap().insert(
+                cache_key,
+                CachedIntrospection {
+                    response: response.clone(),
+                    expires_at: exp,
+                },
+            );
+        }
+
+        Ok(response)
+    }
+}
+
+/// Hash `token` so the introspection cache never holds the raw token value at rest.
+fn introspection_cache_key(token: &str) -> u64 {
+    use sha2::{Digest, Sha256};
+
+    let hash = Sha256::digest(token.as_bytes());
+    u64::from_be_bytes(hash[..8].try_into().expect("sha256 digest is at least 8 bytes"))
+}
+
+/// Returned by [`OpenIdTokenProvider::introspect_token`] when the issuer reports the token as no
+/// longer active (revoked, expired, or otherwise invalid), distinguishing that outcome from a
+/// request/parse failure so callers can react to it specifically (e.g. treat the bearer as
+/// unauthenticated) instead of matching on a generic error string.
+#[derive(Debug, thiserror::Error)]
+pub enum IntrospectionError {
+    #[error("token is not active")]
+    Inactive,
+}
+
+/// Response from an RFC 7662 `/introspect` call. Only `active` is guaranteed; every other claim
+/// is optional since the spec leaves their inclusion up to the authorization server.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct IntrospectionResponse {
+    pub active: bool,
+    pub scope: Option<String>,
+    pub client_id: Option<String>,
+    pub username: Option<String>,
+    pub token_type: Option<String>,
+    pub exp: Option<i64>,
+    pub iat: Option<i64>,
+    pub sub: Option<String>,
+    pub aud: Option<serde_json::Value>,
+    pub iss: Option<String>,
 }
 
 #[async_trait::async_trait]
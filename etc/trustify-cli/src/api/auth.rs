@@ -0,0 +1,398 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use reqwest::Client;
+use serde::Deserialize;
+use thiserror::Error;
+use tokio::sync::RwLock;
+
+#[derive(Error, Debug)]
+pub enum AuthError {
+    #[error("Failed to connect to SSO server: {0}")]
+    ConnectionError(#[from] reqwest::Error),
+
+    #[error("Authentication failed: Invalid client_id, client_secret, or SSO URL. Please verify your credentials.")]
+    AuthenticationFailed,
+
+    #[error("SSO server returned an error: {0}")]
+    ServerError(String),
+}
+
+/// A cached access token alongside the instant at which it should be considered expired
+struct CachedToken {
+    access_token: String,
+    expires_on: Instant,
+}
+
+/// Authentication credentials for token refresh.
+///
+/// Caches the last-fetched token so repeated `get_token` calls don't round-trip to the SSO
+/// server unless the token is missing or near expiry. The cache lives behind an `Arc` so clones
+/// of `AuthCredentials` share it, making it safe to call concurrently from CLI commands.
+///
+/// Renewal uses whichever grant these credentials were built with: the client-credentials grant
+/// when constructed via [`AuthCredentials::new`], or the refresh-token grant when constructed
+/// via [`AuthCredentials::from_device_login`].
+#[derive(Clone)]
+pub struct AuthCredentials {
+    pub token_url: String,
+    pub client_id: String,
+    client_secret: Option<String>,
+    refresh_token: Arc<RwLock<Option<String>>>,
+    cached: Arc<RwLock<Option<CachedToken>>>,
+}
+
+impl AuthCredentials {
+    /// Build credentials from SSO URL and client credentials
+    pub fn new(sso_url: &str, client_id: &str, client_secret: &str) -> Self {
+        let token_url = build_token_url(sso_url);
+        Self {
+            token_url,
+            client_id: client_id.to_string(),
+            client_secret: Some(client_secret.to_string()),
+            refresh_token: Arc::new(RwLock::new(None)),
+            cached: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Build credentials from a completed device-authorization login: seeds the cache with the
+    /// access token already issued, and keeps the refresh token (if the server returned one) so
+    /// later renewals use the refresh-token grant instead of forcing the user back through the
+    /// device flow
+    pub fn from_device_login(
+        sso_url: &str,
+        client_id: &str,
+        access_token: &str,
+        expires_in: Option<Duration>,
+        refresh_token: Option<String>,
+    ) -> Self {
+        let token_url = build_token_url(sso_url);
+        Self {
+            token_url,
+            client_id: client_id.to_string(),
+            client_secret: None,
+            refresh_token: Arc::new(RwLock::new(refresh_token)),
+            cached: Arc::new(RwLock::new(Some(CachedToken {
+                access_token: access_token.to_string(),
+                expires_on: expires_on(expires_in),
+            }))),
+        }
+    }
+
+    /// Get a token using these credentials, reusing the cached one when it's still valid and
+    /// only hitting the SSO endpoint when it's missing or near expiry
+    pub async fn get_token(&self) -> Result<String, AuthError> {
+        if let Some(cached) = self.cached.read().await.as_ref() {
+            if Instant::now() < cached.expires_on {
+                return Ok(cached.access_token.clone());
+            }
+        }
+        self.force_refresh().await
+    }
+
+    /// Bypass the cache and fetch a fresh token, e.g. after a request comes back `401` despite
+    /// a token that looked valid
+    pub async fn force_refresh(&self) -> Result<String, AuthError> {
+        let stored_refresh_token = self.refresh_token.read().await.clone();
+
+        let (access_token, expires_in, new_refresh_token) = match stored_refresh_token {
+            Some(current_refresh_token) => {
+                refresh_token(&self.token_url, &self.client_id, &current_refresh_token).await?
+            }
+            None => {
+                let client_secret = self
+                    .client_secret
+                    .as_deref()
+                    .ok_or(AuthError::AuthenticationFailed)?;
+                let (access_token, expires_in) =
+                    get_token(&self.token_url, &self.client_id, client_secret).await?;
+                (access_token, expires_in, None)
+            }
+        };
+
+        if let Some(new_refresh_token) = new_refresh_token {
+            *self.refresh_token.write().await = Some(new_refresh_token);
+        }
+
+        *self.cached.write().await = Some(CachedToken {
+            access_token: access_token.clone(),
+            expires_on: expires_on(expires_in),
+        });
+
+        Ok(access_token)
+    }
+}
+
+/// Compute the instant at which a freshly issued token should be considered expired, applying
+/// [`expiry_skew`] so renewal happens slightly ahead of the server's own deadline
+fn expires_on(expires_in: Option<Duration>) -> Instant {
+    match expires_in {
+        Some(ttl) => Instant::now() + ttl.saturating_sub(expiry_skew(ttl)),
+        None => Instant::now() + Duration::from_secs(u32::MAX as u64),
+    }
+}
+
+/// Safety margin subtracted from a token's lifetime before we consider it expired: the smaller
+/// of 30 seconds or 10% of the lifetime, so short-lived tokens aren't treated as expired the
+/// instant they're issued
+fn expiry_skew(ttl: Duration) -> Duration {
+    Duration::from_secs(30).min(ttl / 10)
+}
+
+/// Build the token URL from an SSO base URL
+pub fn build_token_url(sso_url: &str) -> String {
+    if sso_url.ends_with("/token") {
+        sso_url.to_string()
+    } else if sso_url.ends_with('/') {
+        format!("{}protocol/openid-connect/token", sso_url)
+    } else {
+        format!("{}/protocol/openid-connect/token", sso_url)
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[allow(dead_code)]
+    token_type: String,
+    expires_in: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: String,
+    error_description: Option<String>,
+}
+
+/// Build the device authorization endpoint URL from an SSO base URL
+pub fn build_device_authorization_url(sso_url: &str) -> String {
+    if sso_url.ends_with('/') {
+        format!("{}protocol/openid-connect/auth/device", sso_url)
+    } else {
+        format!("{}/protocol/openid-connect/auth/device", sso_url)
+    }
+}
+
+#[derive(Deserialize)]
+struct DeviceAuthorizationResponse {
+    device_code: String,
+    user_code: String,
+    verification_uri: String,
+    verification_uri_complete: Option<String>,
+    interval: Option<u64>,
+    expires_in: u64,
+}
+
+/// The server's response to a device authorization request: a code pair for the user to enter
+/// at `verification_uri`, plus how often and how long the client may poll for
+pub struct DeviceAuthorization {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub verification_uri_complete: Option<String>,
+    pub interval: Duration,
+    pub expires_in: Duration,
+}
+
+#[derive(Deserialize)]
+struct DeviceTokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+    refresh_token: Option<String>,
+}
+
+/// Start the OAuth2 device authorization grant (RFC 8628): ask the SSO server for a
+/// device/user code pair that the user can enter at `verification_uri`
+pub async fn start_device_authorization(
+    sso_url: &str,
+    client_id: &str,
+    scope: Option<&str>,
+) -> Result<DeviceAuthorization, AuthError> {
+    let client = Client::new();
+    let device_url = build_device_authorization_url(sso_url);
+
+    let mut form = vec![("client_id", client_id)];
+    if let Some(scope) = scope {
+        form.push(("scope", scope));
+    }
+
+    let response = client.post(&device_url).form(&form).send().await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(AuthError::ServerError(format!("HTTP {}: {}", status, body)));
+    }
+
+    let parsed: DeviceAuthorizationResponse = response.json().await?;
+    Ok(DeviceAuthorization {
+        device_code: parsed.device_code,
+        user_code: parsed.user_code,
+        verification_uri: parsed.verification_uri,
+        verification_uri_complete: parsed.verification_uri_complete,
+        interval: Duration::from_secs(parsed.interval.unwrap_or(5)),
+        expires_in: Duration::from_secs(parsed.expires_in),
+    })
+}
+
+/// Poll the token endpoint until the user completes the device flow, honoring
+/// `authorization_pending` ("keep polling") and `slow_down` ("increase the interval by 5s") as
+/// documented in RFC 8628
+pub async fn poll_device_token(
+    token_url: &str,
+    client_id: &str,
+    authorization: &DeviceAuthorization,
+) -> Result<(String, Option<Duration>, Option<String>), AuthError> {
+    let client = Client::new();
+    let deadline = Instant::now() + authorization.expires_in;
+    let mut interval = authorization.interval;
+
+    loop {
+        if Instant::now() >= deadline {
+            return Err(AuthError::ServerError("Device code expired".to_string()));
+        }
+
+        tokio::time::sleep(interval).await;
+
+        let response = client
+            .post(token_url)
+            .form(&[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+                ("client_id", client_id),
+                ("device_code", &authorization.device_code),
+            ])
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let token_response: DeviceTokenResponse = response.json().await?;
+            return Ok((
+                token_response.access_token,
+                token_response.expires_in.map(Duration::from_secs),
+                token_response.refresh_token,
+            ));
+        }
+
+        let error_response: ErrorResponse = match response.json().await {
+            Ok(e) => e,
+            Err(_) => return Err(AuthError::AuthenticationFailed),
+        };
+
+        match error_response.error.as_str() {
+            "authorization_pending" => continue,
+            "slow_down" => interval += Duration::from_secs(5),
+            "expired_token" => return Err(AuthError::ServerError("Device code expired".to_string())),
+            "access_denied" => return Err(AuthError::AuthenticationFailed),
+            _ => {
+                let msg = error_response
+                    .error_description
+                    .unwrap_or(error_response.error);
+                return Err(AuthError::ServerError(msg));
+            }
+        }
+    }
+}
+
+/// Renew an access token using a previously issued refresh token (e.g. from a device-flow
+/// login) without re-prompting the user
+pub async fn refresh_token(
+    token_url: &str,
+    client_id: &str,
+    refresh_token: &str,
+) -> Result<(String, Option<Duration>, Option<String>), AuthError> {
+    let client = Client::new();
+
+    let response = client
+        .post(token_url)
+        .form(&[
+            ("grant_type", "refresh_token"),
+            ("client_id", client_id),
+            ("refresh_token", refresh_token),
+        ])
+        .send()
+        .await?;
+
+    if response.status().is_success() {
+        let token_response: DeviceTokenResponse = response.json().await?;
+        Ok((
+            token_response.access_token,
+            token_response.expires_in.map(Duration::from_secs),
+            token_response.refresh_token,
+        ))
+    } else {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        Err(AuthError::ServerError(format!("HTTP {}: {}", status, body)))
+    }
+}
+
+/// Run the full interactive device-authorization flow: request a device code, print the
+/// verification URL and user code for the user to open in a browser, then poll until they
+/// complete it. Returns the access token along with its lifetime and refresh token (if the
+/// server issued one), so the caller can build [`AuthCredentials::from_device_login`] and renew
+/// the session later without re-prompting the user.
+pub async fn device_login(
+    sso_url: &str,
+    client_id: &str,
+    scope: Option<&str>,
+) -> Result<(String, Option<Duration>, Option<String>), AuthError> {
+    let authorization = start_device_authorization(sso_url, client_id, scope).await?;
+
+    if let Some(uri) = &authorization.verification_uri_complete {
+        println!("Open {} to log in", uri);
+    } else {
+        println!(
+            "Open {} and enter code: {}",
+            authorization.verification_uri, authorization.user_code
+        );
+    }
+
+    let token_url = build_token_url(sso_url);
+    poll_device_token(&token_url, client_id, &authorization).await
+}
+
+/// Retrieves an OAuth2 access token using the client credentials grant, along with its
+/// lifetime if the SSO server reported one
+pub async fn get_token(
+    token_url: &str,
+    client_id: &str,
+    client_secret: &str,
+) -> Result<(String, Option<Duration>), AuthError> {
+    let client = Client::new();
+
+    let response = client
+        .post(token_url)
+        .form(&[
+            ("grant_type", "client_credentials"),
+            ("client_id", client_id),
+            ("client_secret", client_secret),
+        ])
+        .send()
+        .await?;
+
+    if response.status().is_success() {
+        let token_response: TokenResponse = response.json().await?;
+        Ok((
+            token_response.access_token,
+            token_response.expires_in.map(Duration::from_secs),
+        ))
+    } else if response.status().as_u16() == 401 || response.status().as_u16() == 400 {
+        // Try to get error details
+        if let Ok(error_response) = response.json::<ErrorResponse>().await {
+            if error_response.error == "invalid_client"
+                || error_response.error == "unauthorized_client"
+            {
+                return Err(AuthError::AuthenticationFailed);
+            }
+            let msg = error_response
+                .error_description
+                .unwrap_or(error_response.error);
+            return Err(AuthError::ServerError(msg));
+        }
+        Err(AuthError::AuthenticationFailed)
+    } else {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        Err(AuthError::ServerError(format!("HTTP {}: {}", status, body)))
+    }
+}
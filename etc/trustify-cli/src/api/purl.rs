@@ -0,0 +1,58 @@
+use percent_encoding::{AsciiSet, CONTROLS, utf8_percent_encode};
+use serde::Serialize;
+
+use super::client::{ApiClient, ApiError};
+
+const PURL_PATH: &str = "/v2/purl";
+const BASE_PURL_PATH: &str = "/v2/purl/base";
+const PURL_TYPE_PATH: &str = "/v2/purl/type";
+
+/// Characters that must be percent-encoded in a single path segment, on top of the always-escaped
+/// `CONTROLS`: purls routinely contain `/`, `@`, `:`, and `?`, any of which would otherwise be
+/// parsed as a path separator or the start of a query string
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b'/')
+    .add(b'@')
+    .add(b':')
+    .add(b'?')
+    .add(b'#')
+    .add(b'%')
+    .add(b' ');
+
+/// Query parameters for listing base purls
+#[derive(Default, Serialize)]
+pub struct ListParams {
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub purl_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub q: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u32>,
+}
+
+/// List the distinct purl types known to Trustify (e.g. `maven`, `rpm`, `oci`)
+pub async fn list_types(client: &ApiClient) -> Result<String, ApiError> {
+    client.get(PURL_TYPE_PATH).await
+}
+
+/// List base purls, optionally filtered by type and free-text query
+pub async fn list(client: &ApiClient, params: &ListParams) -> Result<String, ApiError> {
+    client.get_with_query(BASE_PURL_PATH, params).await
+}
+
+/// Fetch full package/vulnerability details for a single purl (accepts either a purl string or
+/// a Trustify UUID)
+pub async fn get(client: &ApiClient, purl_or_uuid: &str) -> Result<String, ApiError> {
+    let encoded = utf8_percent_encode(purl_or_uuid, PATH_SEGMENT);
+    let path = format!("{}/{}", PURL_PATH, encoded);
+    client.get(&path).await
+}
+
+/// List the versioned purls that exist for a given base purl
+pub async fn versions(client: &ApiClient, base_purl: &str) -> Result<String, ApiError> {
+    let encoded = utf8_percent_encode(base_purl, PATH_SEGMENT);
+    let path = format!("{}/{}/versions", BASE_PURL_PATH, encoded);
+    client.get(&path).await
+}
@@ -8,6 +8,13 @@ use std::process;
 pub enum AuthCommands {
     /// Get authentication token
     Token {},
+
+    /// Log in interactively via the OAuth2 device authorization grant, without a client secret
+    Device {
+        /// Optional OAuth2 scope to request
+        #[arg(long)]
+        scope: Option<String>,
+    },
 }
 
 impl AuthCommands {
@@ -29,6 +36,38 @@ impl AuthCommands {
                     process::exit(1);
                 }
             },
+            AuthCommands::Device { scope } => match ctx.config.auth_credentials() {
+                Some((sso_url, client_id, _)) => {
+                    match crate::api::auth::device_login(sso_url, client_id, scope.as_deref())
+                        .await
+                    {
+                        Ok((access_token, expires_in, refresh_token)) => {
+                            let creds = AuthCredentials::from_device_login(
+                                sso_url,
+                                client_id,
+                                &access_token,
+                                expires_in,
+                                refresh_token,
+                            );
+                            match creds.get_token().await {
+                                Ok(token) => println!("{}", token),
+                                Err(e) => {
+                                    eprintln!("Error: {}", e);
+                                    process::exit(1);
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            eprintln!("Error: {}", e);
+                            process::exit(1);
+                        }
+                    }
+                }
+                None => {
+                    eprintln!("Error: SSO URL and client ID are required");
+                    process::exit(1);
+                }
+            },
         }
     }
 }
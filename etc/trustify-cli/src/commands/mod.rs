@@ -1,10 +1,12 @@
 pub mod auth;
+pub mod purl;
 pub mod sbom;
 
 use clap::Subcommand;
 
 use crate::Context;
 pub use auth::AuthCommands;
+pub use purl::PurlCommands;
 pub use sbom::SbomCommands;
 
 #[derive(Subcommand)]
@@ -20,6 +22,12 @@ pub enum Commands {
         #[command(subcommand)]
         command: AuthCommands,
     },
+
+    /// Package URL (purl) query commands
+    Purl {
+        #[command(subcommand)]
+        command: PurlCommands,
+    },
 }
 
 impl Commands {
@@ -27,6 +35,7 @@ impl Commands {
         match self {
             Commands::Sbom { command } => command.run(ctx).await,
             Commands::Auth { command } => command.run(ctx).await,
+            Commands::Purl { command } => command.run(ctx).await,
         }
     }
 }
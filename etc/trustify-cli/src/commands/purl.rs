@@ -0,0 +1,95 @@
+use std::process;
+
+use clap::Subcommand;
+
+use crate::Context;
+use crate::api::purl as purl_api;
+
+#[derive(Subcommand)]
+pub enum PurlCommands {
+    /// List the distinct purl types known to Trustify (e.g. maven, rpm, oci)
+    ListTypes {},
+
+    /// List base purls, optionally filtered by type or free-text query
+    List {
+        /// Restrict results to a single purl type (e.g. "maven")
+        #[arg(long = "type")]
+        r#type: Option<String>,
+
+        /// Free-text filter applied by the server
+        #[arg(long)]
+        filter: Option<String>,
+
+        /// Offset into the result set
+        #[arg(long)]
+        offset: Option<u32>,
+
+        /// Maximum number of results to return
+        #[arg(long)]
+        limit: Option<u32>,
+    },
+
+    /// Get details for a single purl or Trustify UUID
+    Get {
+        /// A purl string (e.g. "pkg:maven/...") or Trustify UUID
+        purl_or_uuid: String,
+    },
+
+    /// List the versions that exist for a base purl
+    Versions {
+        /// The base purl to list versions for
+        base_purl: String,
+    },
+}
+
+impl PurlCommands {
+    pub async fn run(&self, ctx: &Context) {
+        match self {
+            PurlCommands::ListTypes {} => match purl_api::list_types(&ctx.client).await {
+                Ok(json) => println!("{}", json),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    process::exit(1);
+                }
+            },
+            PurlCommands::List {
+                r#type,
+                filter,
+                offset,
+                limit,
+            } => {
+                let params = purl_api::ListParams {
+                    purl_type: r#type.clone(),
+                    q: filter.clone(),
+                    limit: *limit,
+                    offset: *offset,
+                };
+                match purl_api::list(&ctx.client, &params).await {
+                    Ok(json) => println!("{}", json),
+                    Err(e) => {
+                        eprintln!("Error: {}", e);
+                        process::exit(1);
+                    }
+                }
+            }
+            PurlCommands::Get { purl_or_uuid } => {
+                match purl_api::get(&ctx.client, purl_or_uuid).await {
+                    Ok(json) => println!("{}", json),
+                    Err(e) => {
+                        eprintln!("Error: {}", e);
+                        process::exit(1);
+                    }
+                }
+            }
+            PurlCommands::Versions { base_purl } => {
+                match purl_api::versions(&ctx.client, base_purl).await {
+                    Ok(json) => println!("{}", json),
+                    Err(e) => {
+                        eprintln!("Error: {}", e);
+                        process::exit(1);
+                    }
+                }
+            }
+        }
+    }
+}
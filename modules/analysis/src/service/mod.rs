@@ -16,6 +16,13 @@ use crate::{
     config::AnalysisConfig,
     model::{AnalysisStatus, BaseSummary, GraphMap, Node, PackageGraph, graph},
 };
+use arrow::{
+    array::{ArrayRef, StringBuilder, UInt32Builder},
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use async_trait::async_trait;
 use fixedbitset::FixedBitSet;
 use futures::{StreamExt, stream};
 use opentelemetry::global;
@@ -30,11 +37,19 @@ use sea_orm::{
     prelude::ConnectionTrait,
 };
 use sea_query::JoinType;
+use serde::{Deserialize, Serialize};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{HashMap, HashSet, hash_map::DefaultHasher},
     fmt::Debug,
-    sync::Arc,
+    future::Future,
+    hash::{Hash, Hasher},
+    path::PathBuf,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
 };
+use tokio::sync::watch;
 use tracing::instrument;
 use trustify_common::{
     db::query::Value,
@@ -50,9 +65,280 @@ use uuid::Uuid;
 
 type NodeGraph = Graph<graph::Node, Relationship, petgraph::Directed>;
 
+/// Pluggable storage for built `PackageGraph`s, so `AnalysisService` isn't limited to whatever
+/// single eviction strategy `GraphMap` (declared in `crate::model`, not part of this snapshot)
+/// happens to implement.
+///
+/// `GraphMap` itself remains the in-memory tier; `DiskGraphCacheBackend` below is a second tier
+/// that survives restarts, at the cost of a deserialize instead of a cache hit.
+trait GraphCacheBackend: Send + Sync + Debug {
+    /// Fetch a previously stored graph for `sbom_id`, if this tier has one.
+    fn get(&self, sbom_id: &str) -> Option<Arc<PackageGraph>>;
+
+    /// Store `graph` under `sbom_id`.
+    fn put(&self, sbom_id: &str, graph: Arc<PackageGraph>);
+
+    /// Remove any entry for `sbom_id`.
+    fn evict(&self, sbom_id: &str);
+
+    /// Number of entries currently held by this tier.
+    fn len(&self) -> u64;
+
+    /// Approximate bytes of capacity currently in use by this tier.
+    fn size_used(&self) -> u64;
+}
+
+/// On-disk schema version for `DiskGraphCacheBackend` entries.
+///
+/// Bump this whenever `PackageGraph`'s serialized shape changes, so old entries are rejected
+/// (triggering a DB rebuild) instead of being mis-decoded into a corrupt graph.
+const GRAPH_CACHE_FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct StoredGraph {
+    version: u32,
+    graph: PackageGraph,
+}
+
+/// Disk-backed `GraphCacheBackend`: one JSON file per `sbom_id` under `base_dir`, so a warm cache
+/// survives a restart instead of forcing a cold rebuild of every `PackageGraph` from the database.
+///
+/// Mirrors the file-per-key convention already used for the token cache in `src/auth/cache.rs`,
+/// rather than pulling in a new embedded-KV dependency for a single keyed blob store.
+#[derive(Debug)]
+struct DiskGraphCacheBackend {
+    base_dir: PathBuf,
+}
+
+impl DiskGraphCacheBackend {
+    fn new(base_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            base_dir: base_dir.into(),
+        }
+    }
+
+    fn path_for(&self, sbom_id: &str) -> PathBuf {
+        self.base_dir.join(format!("{sbom_id}.json"))
+    }
+}
+
+impl GraphCacheBackend for DiskGraphCacheBackend {
+    fn get(&self, sbom_id: &str) -> Option<Arc<PackageGraph>> {
+        let bytes = std::fs::read(self.path_for(sbom_id)).ok()?;
+        let stored: StoredGraph = serde_json::from_slice(&bytes).ok()?;
+
+        if stored.version != GRAPH_CACHE_FORMAT_VERSION {
+            log::warn!(
+                "ignoring on-disk graph cache entry for sbom {sbom_id}: stored format version {} \
+                 does not match the current version {GRAPH_CACHE_FORMAT_VERSION}",
+                stored.version
+            );
+            return None;
+        }
+
+        Some(Arc::new(stored.graph))
+    }
+
+    fn put(&self, sbom_id: &str, graph: Arc<PackageGraph>) {
+        let stored = StoredGraph {
+            version: GRAPH_CACHE_FORMAT_VERSION,
+            graph: (*graph).clone(),
+        };
+
+        let Ok(bytes) = serde_json::to_vec(&stored) else {
+            return;
+        };
+
+        if std::fs::create_dir_all(&self.base_dir).is_ok() {
+            let _ = std::fs::write(self.path_for(sbom_id), bytes);
+        }
+    }
+
+    fn evict(&self, sbom_id: &str) {
+        let _ = std::fs::remove_file(self.path_for(sbom_id));
+    }
+
+    fn len(&self) -> u64 {
+        std::fs::read_dir(&self.base_dir)
+            .map(|dir| dir.count() as u64)
+            .unwrap_or(0)
+    }
+
+    fn size_used(&self) -> u64 {
+        std::fs::read_dir(&self.base_dir)
+            .map(|dir| {
+                dir.flatten()
+                    .filter_map(|entry| entry.metadata().ok())
+                    .map(|metadata| metadata.len())
+                    .sum()
+            })
+            .unwrap_or(0)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct AnalysisService {
     graph_cache: Arc<GraphMap>,
+    query_cache: Arc<QueryResultCache>,
+    in_flight_loads: Arc<SingleFlight>,
+    disk_cache: Option<Arc<dyn GraphCacheBackend>>,
+}
+
+/// Coordinates concurrent `load_graphs`/`load_graphs_query` builds so that late arrivals for the
+/// same `sbom_id` wait on the first build instead of each independently rebuilding the graph from
+/// the database.
+///
+/// Backed by a `watch` channel rather than `Notify`/`broadcast`: a follower that joins after the
+/// build already finished still observes the "done" value immediately (`wait_for` checks the
+/// current value before waiting), so there's no race between "leader finishes" and "follower
+/// starts waiting".
+#[derive(Debug, Default)]
+struct SingleFlight {
+    in_flight: Mutex<HashMap<Uuid, watch::Receiver<bool>>>,
+}
+
+enum SingleFlightRole {
+    /// This call is responsible for building the value; `complete` must be called once it has.
+    Lead(watch::Sender<bool>),
+    /// A build for this key is already in flight; await its completion.
+    Follow(watch::Receiver<bool>),
+}
+
+impl SingleFlight {
+    /// Join an in-progress build for `key`, or become its leader.
+    fn join_or_lead(&self, key: Uuid) -> SingleFlightRole {
+        let mut in_flight = self.in_flight.lock().unwrap();
+        match in_flight.get(&key) {
+            Some(rx) => SingleFlightRole::Follow(rx.clone()),
+            None => {
+                let (tx, rx) = watch::channel(false);
+                in_flight.insert(key, rx);
+                SingleFlightRole::Lead(tx)
+            }
+        }
+    }
+
+    /// Mark `key`'s build complete: wakes any followers waiting on it, then stops tracking it so
+    /// the next cold miss starts a fresh build.
+    fn complete(&self, key: Uuid, sender: watch::Sender<bool>) {
+        self.in_flight.lock().unwrap().remove(&key);
+        let _ = sender.send(true);
+    }
+
+    /// Stop tracking `key` without signalling success. Used when the leader's build is
+    /// abandoned (cancelled or panicked) instead of finishing normally, so the next caller starts
+    /// a fresh build rather than joining one that will never complete.
+    fn abandon(&self, key: Uuid) {
+        self.in_flight.lock().unwrap().remove(&key);
+    }
+}
+
+/// Removes `key` from its `SingleFlight` when dropped, unless `disarm` was called first.
+///
+/// Guards the leader branch of `dedup_load`: if the future driving it is dropped before `build`
+/// finishes (a timeout, client disconnect, or losing a `select!` - all common in a web server) or
+/// `build` panics, this still runs during unwind/drop and calls `abandon`, instead of leaving a
+/// dead entry in `in_flight` that would permanently poison single-flight for that `sbom_id` -
+/// every subsequent caller would otherwise join the same already-closed receiver forever, and no
+/// one would ever retry the build.
+struct LeaderGuard<'a> {
+    single_flight: &'a SingleFlight,
+    key: Uuid,
+    armed: bool,
+}
+
+impl LeaderGuard<'_> {
+    fn disarm(&mut self) {
+        self.armed = false;
+    }
+}
+
+impl Drop for LeaderGuard<'_> {
+    fn drop(&mut self) {
+        if self.armed {
+            self.single_flight.abandon(self.key);
+        }
+    }
+}
+
+/// A single cached `run_graph_query` result, alongside the set of `sbom_id`s it depends on.
+///
+/// Tracking the dependency set lets us invalidate precisely: when a graph for one of those SBOMs
+/// is evicted or reloaded in `GraphMap`, every entry that referenced it is dropped rather than
+/// served stale.
+#[derive(Clone, Debug)]
+struct QueryResultEntry {
+    sbom_ids: HashSet<String>,
+    nodes: Vec<Node>,
+}
+
+/// Memoizes the (expensive) ancestor/descendant expansion performed by `run_graph_query`.
+///
+/// Modeled on rustc's `QueryCache`: a map of computed results, plus enough bookkeeping of what
+/// each result depended on to invalidate it precisely rather than wholesale.
+#[derive(Debug, Default)]
+struct QueryResultCache {
+    entries: Mutex<HashMap<u64, QueryResultEntry>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl QueryResultCache {
+    /// Compute the cache key for a query over `sbom_ids`, given its (already `.into()`-ed) query
+    /// and options. `GraphQuery`/`QueryOptions` don't expose their internals here, so we fold
+    /// their `Debug` representation into the hash alongside the sorted `sbom_id` set - stable
+    /// enough for memoization, since two equal queries always render identically.
+    fn key(sbom_ids: &[String], query: &impl Debug, options: &impl Debug) -> u64 {
+        let mut sorted_ids = sbom_ids.to_vec();
+        sorted_ids.sort();
+
+        let mut hasher = DefaultHasher::new();
+        sorted_ids.hash(&mut hasher);
+        format!("{query:?}").hash(&mut hasher);
+        format!("{options:?}").hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn get(&self, key: u64) -> Option<Vec<Node>> {
+        let found = self.entries.lock().unwrap().get(&key).map(|e| e.nodes.clone());
+        if found.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+        found
+    }
+
+    fn put(&self, key: u64, sbom_ids: HashSet<String>, nodes: Vec<Node>) {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(key, QueryResultEntry { sbom_ids, nodes });
+    }
+
+    /// Drop every cached result that depends on `sbom_id`.
+    fn invalidate_sbom(&self, sbom_id: &str) {
+        self.entries
+            .lock()
+            .unwrap()
+            .retain(|_, entry| !entry.sbom_ids.contains(sbom_id));
+    }
+
+    fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    fn len(&self) -> u64 {
+        self.entries.lock().unwrap().len() as u64
+    }
+
+    fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -63,127 +349,306 @@ struct ResolvedSbom {
     pub node_id: String,
 }
 
-async fn resolve_external_sbom<C: ConnectionTrait>(
-    node_id: String,
-    connection: &C,
-) -> Option<ResolvedSbom> {
-    // we first lookup in sbom_external_node
-    let sbom_external_node = match sbom_external_node::Entity::find()
-        .filter(sbom_external_node::Column::NodeId.eq(node_id.as_str()))
-        .one(connection)
-        .await
-    {
-        Ok(Some(entity)) => entity,
-        _ => return None,
-    };
+/// Resolves a batch of `sbom_external_node` rows of one `ExternalType` to the real
+/// `(sbom_id, node_id)` they reference, in as few database round trips as possible.
+///
+/// One implementation per `ExternalType`, dispatched by `resolve_external_sbom_batch`, so adding
+/// a new external ecosystem means adding a new impl rather than another `match` arm deep inside
+/// the resolution logic.
+#[async_trait]
+trait ExternalResolver: Send + Sync {
+    /// Resolve every entry in `refs`, keyed by each row's own `node_id` - the id the caller
+    /// originally asked about, as opposed to `external_node_ref`, which is the id *within* the
+    /// resolved document.
+    async fn resolve_batch<C: ConnectionTrait + Sync>(
+        &self,
+        refs: &[sbom_external_node::Model],
+        connection: &C,
+    ) -> HashMap<String, ResolvedSbom>;
+}
 
-    match sbom_external_node.external_type {
-        ExternalType::SPDX => {
-            // For spdx, sbom_external_node discriminator_type and discriminator_value are used
-            // to lookup sbom_id via join to SourceDocument. The node_id is just the external_node_ref.
+/// For SPDX, `discriminator_type`/`discriminator_value` identify the target document's
+/// `source_document.sha256`; the resolved `node_id` is just `external_node_ref`.
+struct SpdxResolver;
 
-            let discriminator_value = sbom_external_node.discriminator_value?;
+#[async_trait]
+impl ExternalResolver for SpdxResolver {
+    async fn resolve_batch<C: ConnectionTrait + Sync>(
+        &self,
+        refs: &[sbom_external_node::Model],
+        connection: &C,
+    ) -> HashMap<String, ResolvedSbom> {
+        let shas: HashSet<String> = refs
+            .iter()
+            .filter_map(|r| match r.discriminator_type {
+                Some(DiscriminatorType::Sha256) => r
+                    .discriminator_value
+                    .clone()
+                    .filter(|value| !value.is_empty()),
+                _ => None,
+            })
+            .collect();
 
-            if discriminator_value.is_empty() {
-                return None;
-            }
+        if shas.is_empty() {
+            return HashMap::new();
+        }
+
+        let matches = sbom::Entity::find()
+            .select_also(source_document::Entity)
+            .join(JoinType::Join, sbom::Relation::SourceDocument.def())
+            .filter(source_document::Column::Sha256.is_in(shas))
+            .all(connection)
+            .await
+            .unwrap_or_default();
+
+        let sbom_id_by_sha: HashMap<String, Uuid> = matches
+            .into_iter()
+            .filter_map(|(sbom, source_document)| Some((source_document?.sha256, sbom.sbom_id)))
+            .collect();
 
-            let query =
-                sbom::Entity::find().join(JoinType::Join, sbom::Relation::SourceDocument.def());
+        refs.iter()
+            .filter(|r| r.discriminator_type == Some(DiscriminatorType::Sha256))
+            .filter_map(|r| {
+                let sbom_id = *sbom_id_by_sha.get(r.discriminator_value.as_ref()?)?;
+                Some((
+                    r.node_id.clone(),
+                    ResolvedSbom {
+                        sbom_id,
+                        node_id: r.external_node_ref.clone(),
+                    },
+                ))
+            })
+            .collect()
+    }
+}
 
-            let query = match sbom_external_node.discriminator_type? {
-                DiscriminatorType::Sha256 => {
-                    query.filter(source_document::Column::Sha256.eq(&discriminator_value))
+/// For CycloneDX, `external_doc_ref`/`discriminator_value` are combined into the target
+/// document's `sbom.document_id` (`urn:cdx:<ref>/<value>`); the resolved `node_id` is
+/// `external_node_ref`.
+struct CycloneDxResolver;
+
+#[async_trait]
+impl ExternalResolver for CycloneDxResolver {
+    async fn resolve_batch<C: ConnectionTrait + Sync>(
+        &self,
+        refs: &[sbom_external_node::Model],
+        connection: &C,
+    ) -> HashMap<String, ResolvedSbom> {
+        let refs_by_doc_id: HashMap<String, Vec<&sbom_external_node::Model>> = refs
+            .iter()
+            .filter_map(|r| {
+                let discriminator_value = r.discriminator_value.as_deref()?;
+                if discriminator_value.is_empty() {
+                    return None;
                 }
-                _ => return None,
-            };
+                let doc_id = format!("urn:cdx:{}/{}", r.external_doc_ref, discriminator_value);
+                Some((doc_id, r))
+            })
+            .fold(HashMap::new(), |mut acc, (doc_id, r)| {
+                acc.entry(doc_id).or_default().push(r);
+                acc
+            });
 
-            match query.one(connection).await {
-                Ok(Some(entity)) => Some(ResolvedSbom {
-                    sbom_id: entity.sbom_id,
-                    node_id: sbom_external_node.external_node_ref,
-                }),
-                _ => None,
-            }
+        if refs_by_doc_id.is_empty() {
+            return HashMap::new();
         }
-        ExternalType::CycloneDx => {
-            // For cyclonedx, sbom_external_node discriminator_type and discriminator_value are used
-            // we construct external_doc_id to lookup sbom_id directly from sbom entity. The node_id
-            // is the external_node_ref
 
-            let discriminator_value = sbom_external_node.discriminator_value?;
+        sbom::Entity::find()
+            .filter(sbom::Column::DocumentId.is_in(refs_by_doc_id.keys().cloned()))
+            .all(connection)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(|sbom| {
+                let sbom_id = sbom.sbom_id;
+                refs_by_doc_id
+                    .get(&sbom.document_id)
+                    .into_iter()
+                    .flatten()
+                    .map(move |r| {
+                        (
+                            r.node_id.clone(),
+                            ResolvedSbom {
+                                sbom_id,
+                                node_id: r.external_node_ref.clone(),
+                            },
+                        )
+                    })
+            })
+            .collect()
+    }
+}
 
-            if discriminator_value.is_empty() {
-                return None;
-            }
+/// For Red Hat product-component variations, `external_node_ref` is assumed to be a package
+/// checksum, first looked up in `sbom_node_checksum` to find its `value`, then matched against
+/// other SBOMs sharing that `value` - falling back to matching `sbom_package` by version when no
+/// checksum is on record.
+struct RedHatProductComponentResolver;
 
-            let external_doc_ref = sbom_external_node.external_doc_ref;
-            let external_doc_id = format!("urn:cdx:{}/{}", external_doc_ref, discriminator_value);
+#[async_trait]
+impl ExternalResolver for RedHatProductComponentResolver {
+    async fn resolve_batch<C: ConnectionTrait + Sync>(
+        &self,
+        refs: &[sbom_external_node::Model],
+        connection: &C,
+    ) -> HashMap<String, ResolvedSbom> {
+        let mut resolved = HashMap::new();
+        let mut remaining: HashMap<String, Vec<&sbom_external_node::Model>> =
+            refs.iter().fold(HashMap::new(), |mut acc, r| {
+                acc.entry(r.external_node_ref.clone()).or_default().push(r);
+                acc
+            });
 
-            match sbom::Entity::find()
-                .filter(sbom::Column::DocumentId.eq(external_doc_id))
-                .one(connection)
-                .await
-            {
-                Ok(Some(entity)) => Some(ResolvedSbom {
-                    sbom_id: entity.sbom_id,
-                    node_id: sbom_external_node.external_node_ref,
-                }),
-                _ => None,
+        if remaining.is_empty() {
+            return resolved;
+        }
+
+        let checksums = sbom_node_checksum::Entity::find()
+            .filter(sbom_node_checksum::Column::NodeId.is_in(remaining.keys().cloned()))
+            .all(connection)
+            .await
+            .unwrap_or_default();
+
+        if !checksums.is_empty() {
+            let values: HashSet<String> = checksums.iter().map(|c| c.value.clone()).collect();
+            let candidates_by_value: HashMap<String, Vec<sbom_node_checksum::Model>> =
+                sbom_node_checksum::Entity::find()
+                    .filter(sbom_node_checksum::Column::Value.is_in(values))
+                    .all(connection)
+                    .await
+                    .unwrap_or_default()
+                    .into_iter()
+                    .fold(HashMap::new(), |mut acc, candidate| {
+                        acc.entry(candidate.value.clone()).or_default().push(candidate);
+                        acc
+                    });
+
+            for checksum in &checksums {
+                let Some(external_refs) = remaining.get(&checksum.node_id) else {
+                    continue;
+                };
+                let Some(candidate) = candidates_by_value
+                    .get(&checksum.value)
+                    .and_then(|candidates| candidates.iter().find(|c| c.sbom_id != checksum.sbom_id))
+                else {
+                    continue;
+                };
+                for external_ref in external_refs {
+                    resolved.insert(
+                        external_ref.node_id.clone(),
+                        ResolvedSbom {
+                            sbom_id: candidate.sbom_id,
+                            node_id: candidate.node_id.clone(),
+                        },
+                    );
+                }
+                remaining.remove(&checksum.node_id);
             }
         }
-        ExternalType::RedHatProductComponent => {
-            // for RH variations we assume the sbom_external_node_ref is the package checksum
-            // which is used on sbom_node_checksum to lookup related value then
-            // perform another lookup on sbom_node_checksum (matching by value) to find resultant
-            // sbom_id/node_id
-            let sbom_external_node_ref = sbom_external_node.external_node_ref;
-
-            match sbom_node_checksum::Entity::find()
-                .filter(sbom_node_checksum::Column::NodeId.eq(sbom_external_node_ref.to_string()))
-                .one(connection)
+
+        if remaining.is_empty() {
+            return resolved;
+        }
+
+        let packages = sbom_package::Entity::find()
+            .filter(sbom_package::Column::NodeId.is_in(remaining.keys().cloned()))
+            .all(connection)
+            .await
+            .unwrap_or_default();
+
+        if packages.is_empty() {
+            return resolved;
+        }
+
+        let versions: HashSet<String> = packages.iter().map(|p| p.version.clone()).collect();
+        let candidates_by_version: HashMap<String, Vec<sbom_package::Model>> =
+            sbom_package::Entity::find()
+                .filter(sbom_package::Column::Version.is_in(versions))
+                .all(connection)
                 .await
-            {
-                Ok(Some(entity)) => {
-                    match sbom_node_checksum::Entity::find()
-                        .filter(sbom_node_checksum::Column::SbomId.ne(entity.sbom_id))
-                        .filter(sbom_node_checksum::Column::Value.eq(entity.value.to_string()))
-                        .one(connection)
-                        .await
-                    {
-                        Ok(Some(matched)) => Some(ResolvedSbom {
-                            sbom_id: matched.sbom_id,
-                            node_id: matched.node_id,
-                        }),
-                        _ => None,
-                    }
-                }
-                _ => {
-                    match sbom_package::Entity::find()
-                        .filter(sbom_package::Column::NodeId.eq(sbom_external_node_ref.clone()))
-                        .one(connection)
-                        .await
-                    {
-                        Ok(Some(imagevariant)) => {
-                            match sbom_package::Entity::find()
-                                .filter(sbom_package::Column::SbomId.ne(imagevariant.sbom_id))
-                                .filter(sbom_package::Column::Version.eq(imagevariant.version))
-                                .one(connection)
-                                .await
-                            {
-                                Ok(Some(matched_imagevariant)) => Some(ResolvedSbom {
-                                    sbom_id: matched_imagevariant.sbom_id,
-                                    node_id: matched_imagevariant.node_id,
-                                }),
-                                _ => None,
-                            }
-                        }
-                        _ => None,
-                    }
-                }
+                .unwrap_or_default()
+                .into_iter()
+                .fold(HashMap::new(), |mut acc, candidate| {
+                    acc.entry(candidate.version.clone()).or_default().push(candidate);
+                    acc
+                });
+
+        for package in &packages {
+            let Some(external_refs) = remaining.get(&package.node_id) else {
+                continue;
+            };
+            let Some(candidate) = candidates_by_version
+                .get(&package.version)
+                .and_then(|candidates| candidates.iter().find(|c| c.sbom_id != package.sbom_id))
+            else {
+                continue;
+            };
+            for external_ref in external_refs {
+                resolved.insert(
+                    external_ref.node_id.clone(),
+                    ResolvedSbom {
+                        sbom_id: candidate.sbom_id,
+                        node_id: candidate.node_id.clone(),
+                    },
+                );
             }
         }
+
+        resolved
+    }
+}
+
+/// Resolve a whole traversal frontier of external node refs in O(types) queries instead of
+/// O(n): one shared lookup groups `node_ids` by `ExternalType`, then each group is resolved with
+/// a single batched call to the matching `ExternalResolver`, instead of the one-row-at-a-time
+/// lookups this used to issue per node.
+async fn resolve_external_sbom_batch<C: ConnectionTrait>(
+    node_ids: &[String],
+    connection: &C,
+) -> HashMap<String, ResolvedSbom> {
+    if node_ids.is_empty() {
+        return HashMap::new();
+    }
+
+    let external_nodes = sbom_external_node::Entity::find()
+        .filter(sbom_external_node::Column::NodeId.is_in(node_ids.iter().cloned()))
+        .all(connection)
+        .await
+        .unwrap_or_default();
+
+    let by_type: HashMap<ExternalType, Vec<sbom_external_node::Model>> =
+        external_nodes.into_iter().fold(HashMap::new(), |mut acc, node| {
+            acc.entry(node.external_type).or_default().push(node);
+            acc
+        });
+
+    let mut resolved = HashMap::new();
+    for (external_type, refs) in by_type {
+        let batch = match external_type {
+            ExternalType::SPDX => SpdxResolver.resolve_batch(&refs, connection).await,
+            ExternalType::CycloneDx => CycloneDxResolver.resolve_batch(&refs, connection).await,
+            ExternalType::RedHatProductComponent => {
+                RedHatProductComponentResolver
+                    .resolve_batch(&refs, connection)
+                    .await
+            }
+        };
+        resolved.extend(batch);
     }
+
+    resolved
+}
+
+/// Single-node convenience wrapper around `resolve_external_sbom_batch`, kept so any caller not
+/// yet updated to resolve a whole traversal frontier at once (e.g. `Collector`, in `collector.rs`,
+/// which is not part of this snapshot) keeps working unchanged.
+async fn resolve_external_sbom<C: ConnectionTrait>(
+    node_id: String,
+    connection: &C,
+) -> Option<ResolvedSbom> {
+    resolve_external_sbom_batch(std::slice::from_ref(&node_id), connection)
+        .await
+        .remove(&node_id)
 }
 
 impl AnalysisService {
@@ -200,6 +665,7 @@ impl AnalysisService {
     /// of having its own cache. So creating a new instance should be a deliberate choice.
     pub fn new(config: AnalysisConfig) -> Self {
         let graph_cache = Arc::new(GraphMap::new(config.max_cache_size.as_u64()));
+        let query_cache = Arc::new(QueryResultCache::default());
 
         let meter = global::meter("AnalysisService");
         {
@@ -216,8 +682,79 @@ impl AnalysisService {
                 .with_callback(move |inst| inst.observe(graph_cache.len(), &[]))
                 .build();
         };
+        {
+            let query_cache = query_cache.clone();
+            meter
+                .u64_observable_gauge("query_cache_items")
+                .with_callback(move |inst| inst.observe(query_cache.len(), &[]))
+                .build();
+        };
+        {
+            let query_cache = query_cache.clone();
+            meter
+                .u64_observable_gauge("query_cache_hits")
+                .with_callback(move |inst| inst.observe(query_cache.hits(), &[]))
+                .build();
+        };
+        {
+            let query_cache = query_cache.clone();
+            meter
+                .u64_observable_gauge("query_cache_misses")
+                .with_callback(move |inst| inst.observe(query_cache.misses(), &[]))
+                .build();
+        };
 
-        Self { graph_cache }
+        Self {
+            graph_cache,
+            query_cache,
+            in_flight_loads: Arc::new(SingleFlight::default()),
+            disk_cache: None,
+        }
+    }
+
+    /// Like `new`, but also wires up a disk-backed second cache tier at `cache_dir`.
+    ///
+    /// On an in-memory `GraphMap` miss, `load_graphs`/`load_graphs_query` (in `load.rs`, not part
+    /// of this snapshot) are expected to consult `disk_cache()` before falling back to a full
+    /// database rebuild, and to `put` newly built graphs into it so a restart starts warm.
+    pub fn with_disk_cache(config: AnalysisConfig, cache_dir: impl Into<PathBuf>) -> Self {
+        let mut service = Self::new(config);
+        service.disk_cache = Some(Arc::new(DiskGraphCacheBackend::new(cache_dir)));
+        service
+    }
+
+    pub(crate) fn disk_cache(&self) -> Option<&Arc<dyn GraphCacheBackend>> {
+        self.disk_cache.as_ref()
+    }
+
+    /// Coordinate a single build for `sbom_id` across concurrent callers.
+    ///
+    /// `build` runs only for the caller that arrives first; concurrent callers for the same
+    /// `sbom_id` await its completion instead of duplicating the (expensive) database rebuild.
+    /// Returns `true` if the build completed and the cache was populated, `false` if the leader's
+    /// build was cancelled or panicked before finishing - callers must not assume the cache is
+    /// populated on `false` and should rebuild (or propagate the failure) themselves. This is the
+    /// hook `load_graphs`/`load_graphs_query` (declared via `mod load;`, not part of this
+    /// snapshot) are expected to wrap their per-`sbom_id` graph construction in.
+    pub async fn dedup_load<F, Fut>(&self, sbom_id: Uuid, build: F) -> bool
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        match self.in_flight_loads.join_or_lead(sbom_id) {
+            SingleFlightRole::Lead(sender) => {
+                let mut guard = LeaderGuard {
+                    single_flight: &self.in_flight_loads,
+                    key: sbom_id,
+                    armed: true,
+                };
+                build().await;
+                guard.disarm();
+                self.in_flight_loads.complete(sbom_id, sender);
+                true
+            }
+            SingleFlightRole::Follow(mut rx) => rx.wait_for(|&done| done).await.is_ok(),
+        }
     }
 
     pub fn cache_size_used(&self) -> u64 {
@@ -228,6 +765,53 @@ impl AnalysisService {
         self.graph_cache.len()
     }
 
+    pub fn query_cache_len(&self) -> u64 {
+        self.query_cache.len()
+    }
+
+    /// Export `nodes` (e.g. a `retrieve`/`retrieve_single` result) as Apache Arrow
+    /// `RecordBatch`es, `batch_size` rows at a time, instead of forcing bulk consumers through
+    /// N+1 JSON round-trips over `paginate_array`.
+    ///
+    /// Every node visited while walking `ancestors`/`descendants` becomes its own row, tagged
+    /// with its `depth` and `parent_node_id` so the original tree shape can be reconstructed
+    /// downstream (e.g. in a DataFrame `groupby`).
+    pub fn export_arrow(
+        &self,
+        nodes: &[Node],
+        batch_size: usize,
+    ) -> Result<Vec<RecordBatch>, ArrowError> {
+        let mut rows = Vec::new();
+        flatten_arrow_rows(nodes, 0, None, &mut rows);
+
+        rows.chunks(batch_size.max(1))
+            .map(arrow_row_batch)
+            .collect()
+    }
+
+    /// Like `export_arrow`, but returns an iterator of batches rather than a materialized `Vec`,
+    /// so a streaming transport (e.g. an Arrow Flight `DoGet`) can hand rows to the client
+    /// incrementally. The flattening itself still runs eagerly up front, since it walks the
+    /// already-fully-collected `ancestors`/`descendants` trees produced by `run_graph_query`;
+    /// only the construction of each `RecordBatch` is deferred to iteration.
+    pub fn export_arrow_iter(
+        &self,
+        nodes: &[Node],
+        batch_size: usize,
+    ) -> impl Iterator<Item = Result<RecordBatch, ArrowError>> {
+        let mut rows = Vec::new();
+        flatten_arrow_rows(nodes, 0, None, &mut rows);
+
+        let batch_size = batch_size.max(1);
+        let chunk_count = rows.len().div_ceil(batch_size);
+
+        (0..chunk_count).map(move |chunk| {
+            let start = chunk * batch_size;
+            let end = (start + batch_size).min(rows.len());
+            arrow_row_batch(&rows[start..end])
+        })
+    }
+
     #[instrument(skip_all, err)]
     pub async fn load_all_graphs<C: ConnectionTrait>(
         &self,
@@ -248,9 +832,20 @@ impl AnalysisService {
 
     pub fn clear_all_graphs(&self) -> Result<(), Error> {
         self.graph_cache.clear();
+        self.query_cache.clear();
         Ok(())
     }
 
+    /// Drop every cached `run_graph_query` result that depends on `sbom_id`.
+    ///
+    /// Call this whenever `sbom_id`'s graph is evicted or reloaded in `GraphMap`, so stale
+    /// dependency trees computed against the old graph are never served from the query cache.
+    /// That eviction/reload currently happens inside `load_graphs`/`load_graphs_query` (declared
+    /// via `mod load;`), which is not part of this snapshot - this is the hook they should call.
+    pub fn invalidate_query_cache(&self, sbom_id: &str) {
+        self.query_cache.invalidate_sbom(sbom_id);
+    }
+
     pub async fn status<C: ConnectionTrait>(
         &self,
         connection: &C,
@@ -276,22 +871,25 @@ impl AnalysisService {
     {
         let query = query.into();
 
-        stream::iter(
-            graphs
-                .iter()
-                .filter(|(sbom_id, graph)| acyclic(sbom_id, graph)),
-        )
-        .flat_map(|(_, graph)| {
-            stream::iter(
-                graph
-                    .node_indices()
-                    .filter(|&i| Self::filter(graph, &query, i))
-                    .filter_map(|i| graph.node_weight(i).map(|w| (i, w))),
-            )
-            .then(|(node_index, package_node)| create(graph, node_index, package_node))
-        })
-        .collect::<Vec<_>>()
-        .await
+        // A single circular reference no longer disqualifies the whole SBOM: `acyclic` now only
+        // logs a warning for visibility, while the actual cycle breaking happens per traversal,
+        // inside `Collector`, which stops descending into a node already on the active path.
+        for (sbom_id, graph) in graphs {
+            acyclic(sbom_id, graph);
+        }
+
+        stream::iter(graphs.iter())
+            .flat_map(|(_, graph)| {
+                stream::iter(
+                    graph
+                        .node_indices()
+                        .filter(|&i| Self::filter(graph, &query, i))
+                        .filter_map(|i| graph.node_weight(i).map(|w| (i, w))),
+                )
+                .then(|(node_index, package_node)| create(graph, node_index, package_node))
+            })
+            .collect::<Vec<_>>()
+            .await
     }
 
     #[instrument(skip(self, connection, graph_cache))]
@@ -364,13 +962,7 @@ impl AnalysisService {
 
         let graphs = self.load_graphs(connection, &distinct_sbom_ids).await?;
         let components = self
-            .run_graph_query(
-                query,
-                options,
-                &graphs,
-                connection,
-                self.graph_cache.clone(),
-            )
+            .cached_run_graph_query(query, options, &graphs, connection)
             .await;
 
         Ok(paginated.paginate_array(&components))
@@ -390,17 +982,49 @@ impl AnalysisService {
 
         let graphs = self.load_graphs_query(connection, query).await?;
 
+        let components = self
+            .cached_run_graph_query(query, options, &graphs, connection)
+            .await;
+
+        Ok(paginated.paginate_array(&components))
+    }
+
+    /// Probe the query-result cache before paying for a full `run_graph_query` expansion.
+    ///
+    /// The cache key is derived from the *loaded* graphs' `sbom_id`s rather than the caller's
+    /// input, so `retrieve` (which resolves `sbom_id`s from the query itself) and
+    /// `retrieve_single` key identically.
+    async fn cached_run_graph_query<'a, C: ConnectionTrait>(
+        &self,
+        query: impl Into<GraphQuery<'a>> + Debug,
+        options: impl Into<QueryOptions> + Debug,
+        graphs: &[(String, Arc<PackageGraph>)],
+        connection: &C,
+    ) -> Vec<Node> {
+        let query = query.into();
+        let options = options.into();
+
+        let sbom_ids: Vec<String> = graphs.iter().map(|(id, _)| id.clone()).collect();
+        let cache_key = QueryResultCache::key(&sbom_ids, &query, &options);
+
+        if let Some(components) = self.query_cache.get(cache_key) {
+            return components;
+        }
+
         let components = self
             .run_graph_query(
                 query,
                 options,
-                &graphs,
+                graphs,
                 connection,
                 self.graph_cache.clone(),
             )
             .await;
 
-        Ok(paginated.paginate_array(&components))
+        self.query_cache
+            .put(cache_key, sbom_ids.into_iter().collect(), components.clone());
+
+        components
     }
 
     /// check if a node in the graph matches the provided query
@@ -458,7 +1082,14 @@ impl AnalysisService {
     }
 }
 
-fn acyclic(id: &str, graph: &Arc<PackageGraph>) -> bool {
+/// Diagnostic-only: logs a warning if `graph` contains a circular reference.
+///
+/// This no longer gates whether the graph is analyzable — a single cycle anywhere used to
+/// disqualify the entire SBOM. Cycles are now broken per traversal, inside `Collector`, by
+/// tracking the `NodeIndex` values on the active path and refusing to re-descend into one that's
+/// already there. This function stays around purely so operators still see a log line pointing at
+/// the offending edge.
+fn acyclic(id: &str, graph: &Arc<PackageGraph>) {
     use petgraph::visit::{DfsEvent, depth_first_search};
     let g = graph.as_ref();
     let result = depth_first_search(g, g.node_identifiers(), |event| match event {
@@ -467,12 +1098,116 @@ fn acyclic(id: &str, graph: &Arc<PackageGraph>) -> bool {
     })
     .err();
     if let Some((start, end)) = result {
-        // FIXME: we need a better strategy handling such errors
         let start = graph.node_weight(start);
         let end = graph.node_weight(end);
         log::warn!(
             "analysis graph of sbom {id} has circular references (detected: {start:?} -> {end:?})!",
         );
     }
-    result.is_none()
+}
+
+/// One flattened row of `AnalysisService::export_arrow`'s Arrow output.
+///
+/// Field names mirror the `sbom_id`/`node_id`/`name`/`version`/`purl`/`cpe` keys `filter()`
+/// already pulls out of the equivalent `graph::Node` data for query matching above; `purl`/`cpe`
+/// take only the first value when a node carries more than one, since each Arrow column is
+/// single-valued.
+struct ArrowRow {
+    sbom_id: String,
+    node_id: String,
+    name: String,
+    purl: Option<String>,
+    cpe: Option<String>,
+    version: Option<String>,
+    relationship: Option<String>,
+    depth: u32,
+    parent_node_id: Option<String>,
+}
+
+fn arrow_schema() -> Arc<Schema> {
+    Arc::new(Schema::new(vec![
+        Field::new("sbom_id", DataType::Utf8, false),
+        Field::new("node_id", DataType::Utf8, false),
+        Field::new("name", DataType::Utf8, false),
+        Field::new("purl", DataType::Utf8, true),
+        Field::new("cpe", DataType::Utf8, true),
+        Field::new("version", DataType::Utf8, true),
+        Field::new("relationship", DataType::Utf8, true),
+        Field::new("depth", DataType::UInt32, false),
+        Field::new("parent_node_id", DataType::Utf8, true),
+    ]))
+}
+
+/// Recursively flatten `nodes` and their `ancestors`/`descendants` into `rows`, tagging each with
+/// its distance from the root (`depth`) and the `node_id` it was reached from (`parent_node_id`).
+fn flatten_arrow_rows(
+    nodes: &[Node],
+    depth: u32,
+    parent_node_id: Option<&str>,
+    rows: &mut Vec<ArrowRow>,
+) {
+    for node in nodes {
+        let base = &node.base;
+
+        rows.push(ArrowRow {
+            sbom_id: base.sbom_id.to_string(),
+            node_id: base.node_id.to_string(),
+            name: base.name.to_string(),
+            purl: base.purl.first().map(ToString::to_string),
+            cpe: base.cpe.first().map(ToString::to_string),
+            version: Some(base.version.to_string()).filter(|v| !v.is_empty()),
+            relationship: node.relationship.as_ref().map(|r| format!("{r:?}")),
+            depth,
+            parent_node_id: parent_node_id.map(ToString::to_string),
+        });
+
+        flatten_arrow_rows(&node.ancestors, depth + 1, Some(&base.node_id), rows);
+        flatten_arrow_rows(&node.descendants, depth + 1, Some(&base.node_id), rows);
+    }
+}
+
+fn arrow_row_batch(rows: &[ArrowRow]) -> Result<RecordBatch, ArrowError> {
+    let mut sbom_id = StringBuilder::new();
+    let mut node_id = StringBuilder::new();
+    let mut name = StringBuilder::new();
+    let mut purl = StringBuilder::new();
+    let mut cpe = StringBuilder::new();
+    let mut version = StringBuilder::new();
+    let mut relationship = StringBuilder::new();
+    let mut depth = UInt32Builder::new();
+    let mut parent_node_id = StringBuilder::new();
+
+    for row in rows {
+        sbom_id.append_value(&row.sbom_id);
+        node_id.append_value(&row.node_id);
+        name.append_value(&row.name);
+        append_opt(&mut purl, &row.purl);
+        append_opt(&mut cpe, &row.cpe);
+        append_opt(&mut version, &row.version);
+        append_opt(&mut relationship, &row.relationship);
+        depth.append_value(row.depth);
+        append_opt(&mut parent_node_id, &row.parent_node_id);
+    }
+
+    RecordBatch::try_new(
+        arrow_schema(),
+        vec![
+            Arc::new(sbom_id.finish()) as ArrayRef,
+            Arc::new(node_id.finish()),
+            Arc::new(name.finish()),
+            Arc::new(purl.finish()),
+            Arc::new(cpe.finish()),
+            Arc::new(version.finish()),
+            Arc::new(relationship.finish()),
+            Arc::new(depth.finish()),
+            Arc::new(parent_node_id.finish()),
+        ],
+    )
+}
+
+fn append_opt(builder: &mut StringBuilder, value: &Option<String>) {
+    match value {
+        Some(value) => builder.append_value(value),
+        None => builder.append_null(),
+    }
 }
@@ -6,6 +6,7 @@ use crate::{
     },
 };
 use actix_web::{HttpResponse, Responder, get, post, web};
+use serde::Serialize;
 use trustify_auth::{ReadSbom, authorizer::Require};
 use trustify_common::{
     db::{Database, query::Query},
@@ -19,6 +20,25 @@ mod base;
 mod r#type;
 mod version;
 
+/// Structured, machine-readable body for a failed purl lookup, so a client can dispatch on
+/// `error_code` instead of pattern-matching the human-readable `message`.
+#[derive(Serialize)]
+struct ErrorBody {
+    error_code: &'static str,
+    error_type: &'static str,
+    message: String,
+}
+
+impl ErrorBody {
+    fn new(error_code: &'static str, message: impl ToString) -> Self {
+        Self {
+            error_code,
+            error_type: "internal",
+            message: message.to_string(),
+        }
+    }
+}
+
 pub fn configure(config: &mut utoipa_actix_web::service_config::ServiceConfig, db: Database) {
     let purl_service = PurlService::new();
 
@@ -73,8 +93,10 @@ pub async fn get(
             Some(detail) => Ok(HttpResponse::Ok().json(detail)),
             None => Ok(HttpResponse::NotFound().body("Identifier not found")),
         },
-        Err(error) => Ok(HttpResponse::InternalServerError()
-            .body(format!("Error fetching purl {result_key}: {}", error))),
+        Err(error) => Ok(HttpResponse::InternalServerError().json(ErrorBody::new(
+            "purl_fetch_failed",
+            format!("Error fetching purl {result_key}: {}", error),
+        ))),
     }
 }
 
@@ -106,9 +128,10 @@ pub async fn get_multiple(
         .await
     {
         Ok(details) => Ok(HttpResponse::Ok().json(details)),
-        Err(error) => Ok(
-            HttpResponse::InternalServerError().body(format!("Error fetching purls: {}", error))
-        ),
+        Err(error) => Ok(HttpResponse::InternalServerError().json(ErrorBody::new(
+            "purls_fetch_failed",
+            format!("Error fetching purls: {}", error),
+        ))),
     }
 }
 
@@ -7,11 +7,12 @@ use crate::{
         summary::{base_purl::BasePurlSummary, purl::PurlSummary, r#type::TypeSummary},
     },
 };
+use base64::Engine;
 use sea_orm::{
     ColumnTrait, ColumnType, ConnectionTrait, EntityTrait, FromQueryResult, IntoIdentity,
     QueryFilter, QueryOrder, QuerySelect, prelude::Uuid,
 };
-use sea_query::{Expr, Func, Order, SimpleExpr};
+use sea_query::{Condition, Expr, Func, Order, SimpleExpr};
 use std::{collections::HashMap, fmt::Debug, str::FromStr};
 use tracing::instrument;
 use trustify_common::{
@@ -275,34 +276,58 @@ impl PurlService {
         connection: &C,
         ingestor: &IngestorService,
     ) {
-        let ingestion_futures: Vec<_> = purls
-            .iter()
-            .map(|purl| {
-                // Clone the package URL if needed (depending on its type).
-                let purl = purl.clone();
-                async move {
-                    match ingestor
+        match self.ingest_purls(purls, connection, ingestor).await {
+            Ok(results) => {
+                for result in results {
+                    if let PurlIngestStatus::Failed { message } = result.status {
+                        log::error!("Failed to ingest package {}: {}", result.purl, message);
+                    }
+                }
+            }
+            Err(e) => log::error!("Failed to ingest purls: {:?}", e),
+        }
+    }
+
+    /// Ingest each of `purls` that doesn't already have a qualified package, reporting a
+    /// per-purl [`PurlIngestResult`] instead of only logging failures. Lets a batch ingestion
+    /// endpoint return a result row per requested purl, the way a batch object-store API
+    /// returns a per-key status, so callers can retry only the purls that failed rather than
+    /// resubmitting the whole batch.
+    pub async fn ingest_purls<C: ConnectionTrait>(
+        &self,
+        purls: &[Purl],
+        connection: &C,
+        ingestor: &IngestorService,
+    ) -> Result<Vec<PurlIngestResult>, Error> {
+        let ingestion_futures = purls.iter().map(|purl| {
+            // Clone the package URL if needed (depending on its type).
+            let purl = purl.clone();
+            async move {
+                let status = match ingestor
+                    .graph()
+                    .get_qualified_package(&purl, connection)
+                    .await
+                {
+                    Ok(Some(_)) => PurlIngestStatus::AlreadyExisted,
+                    Ok(None) => match ingestor
                         .graph()
-                        .get_qualified_package(&purl, connection)
+                        .ingest_qualified_package(&purl, connection)
                         .await
                     {
-                        Ok(Some(_)) => (), // Package exists, do nothing.
-                        Ok(None) => {
-                            if let Err(e) = ingestor
-                                .graph()
-                                .ingest_qualified_package(&purl, connection)
-                                .await
-                            {
-                                log::error!("Failed to ingest package {}: {:?}", purl, e);
-                            }
-                        }
-                        Err(e) => log::error!("Failed to check package {}: {:?}", purl, e),
-                    }
-                }
-            })
-            .collect();
+                        Ok(created) => PurlIngestStatus::Ingested { uuid: created.id },
+                        Err(e) => PurlIngestStatus::Failed {
+                            message: e.to_string(),
+                        },
+                    },
+                    Err(e) => PurlIngestStatus::Failed {
+                        message: e.to_string(),
+                    },
+                };
+                PurlIngestResult { purl, status }
+            }
+        });
 
-        futures_util::future::join_all(ingestion_futures).await;
+        Ok(futures_util::future::join_all(ingestion_futures).await)
     }
 
     async fn purls_by_purl<C: ConnectionTrait>(
@@ -417,6 +442,95 @@ impl PurlService {
         })
     }
 
+    /// Opt-in keyset (cursor) pagination for [`base_purls`](Self::base_purls): orders by the
+    /// stable `(type, namespace, name)` key and filters with `WHERE key > :cursor ... LIMIT n`
+    /// instead of `OFFSET`, so paging stays proportional to `limit` instead of degrading at
+    /// large offsets. `after` is the opaque `next_cursor` returned by a previous page.
+    #[instrument(skip(self, connection), err)]
+    pub async fn base_purls_keyset<C: ConnectionTrait>(
+        &self,
+        query: Query,
+        after: Option<&str>,
+        limit: u64,
+        connection: &C,
+    ) -> Result<KeysetPage<BasePurlSummary>, Error> {
+        let mut find = base_purl::Entity::find().filtering(query)?;
+
+        if let Some(cursor) = after.and_then(decode_base_purl_cursor) {
+            find = find.filter(base_purl_cursor_filter(&cursor));
+        }
+
+        let items = find
+            .order_by_asc(base_purl::Column::Type)
+            .order_by_asc(base_purl::Column::Namespace)
+            .order_by_asc(base_purl::Column::Name)
+            .limit(limit)
+            .all(connection)
+            .await?;
+
+        let next_cursor = items.last().map(|last| {
+            encode_cursor(&BasePurlCursor {
+                r#type: last.r#type.clone(),
+                namespace: last.namespace.clone(),
+                name: last.name.clone(),
+            })
+        });
+
+        Ok(KeysetPage {
+            items: BasePurlSummary::from_entities(&items).await?,
+            next_cursor,
+        })
+    }
+
+    /// Opt-in keyset (cursor) pagination for [`purls`](Self::purls): orders by the qualified
+    /// purl's `Id` instead of using `OFFSET`. `after` is the opaque `next_cursor` returned by a
+    /// previous page.
+    #[instrument(skip(self, connection), err)]
+    pub async fn purls_keyset<C: ConnectionTrait>(
+        &self,
+        query: Query,
+        after: Option<&str>,
+        limit: u64,
+        connection: &C,
+    ) -> Result<KeysetPage<PurlSummary>, Error> {
+        let mut find = qualified_purl::Entity::find().filtering_with(
+            query,
+            qualified_purl::Entity
+                .columns()
+                .json_keys("purl", &["ty", "namespace", "name", "version"])
+                .json_keys("qualifiers", &["arch", "distro", "repository_url"])
+                .translator(|f, op, v| match f {
+                    "type" => Some(format!("ty{op}{v}")),
+                    _ => None,
+                })
+                .add_expr(
+                    "purl",
+                    SimpleExpr::FunctionCall(
+                        Func::cust("get_purl".into_identity())
+                            .arg(Expr::col(qualified_purl::Column::Id)),
+                    ),
+                    ColumnType::Text,
+                ),
+        )?;
+
+        if let Some(cursor) = after.and_then(decode_uuid_cursor) {
+            find = find.filter(qualified_purl::Column::Id.gt(cursor));
+        }
+
+        let items = find
+            .order_by_asc(qualified_purl::Column::Id)
+            .limit(limit)
+            .all(connection)
+            .await?;
+
+        let next_cursor = items.last().map(|last| encode_uuid_cursor(last.id));
+
+        Ok(KeysetPage {
+            items: PurlSummary::from_entities(&items),
+            next_cursor,
+        })
+    }
+
     #[instrument(skip(self, connection), err)]
     pub async fn gc_purls<C: ConnectionTrait>(&self, connection: &C) -> Result<u64, Error> {
         let res = connection
@@ -427,5 +541,110 @@ impl PurlService {
     }
 }
 
+/// A page of results produced by keyset (cursor) pagination, as an opt-in alternative to the
+/// offset/limit `Paginated` that `purls`/`base_purls` accept. Unlike `PaginatedResults`, it
+/// carries no `total` since counting the whole result set would defeat the point of avoiding
+/// `OFFSET`.
+pub struct KeysetPage<T> {
+    pub items: Vec<T>,
+    /// Opaque cursor encoding the last row's key; pass as `after` to fetch the next page. `None`
+    /// only when this page is empty; a final page that is non-empty still carries a cursor, and
+    /// the page fetched with it comes back empty
+    pub next_cursor: Option<String>,
+}
+
+/// The outcome of ingesting a single purl via [`PurlService::ingest_purls`]
+pub enum PurlIngestStatus {
+    /// A qualified package already existed for this purl; nothing was ingested
+    AlreadyExisted,
+    /// A new qualified package was created
+    Ingested { uuid: Uuid },
+    /// Ingestion failed; the purl can be retried independently of the rest of the batch
+    Failed { message: String },
+}
+
+/// Per-purl result returned by [`PurlService::ingest_purls`], so a caller submitting many purls
+/// can tell which ones succeeded and retry only the ones that didn't
+pub struct PurlIngestResult {
+    pub purl: Purl,
+    pub status: PurlIngestStatus,
+}
+
+/// The `(type, namespace, name)` keyset cursor used by `base_purls_keyset`
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BasePurlCursor {
+    r#type: String,
+    namespace: Option<String>,
+    name: String,
+}
+
+/// Advances past every row already seen under `ORDER BY type, namespace, name` (Postgres'
+/// default `NULLS LAST` for an ascending order).
+///
+/// `namespace` is nullable, so this can't be a single row-wise tuple comparison: SQL's `(a, b,
+/// c) > (x, y, z)` evaluates to `NULL` (never `true`) as soon as any compared column is `NULL`
+/// before the earlier columns have differentiated the rows, which would silently drop every
+/// row sharing `cursor.type` with a `NULL` namespace from all subsequent pages. Instead, compare
+/// in stages, matching `NULLS LAST` explicitly at the `namespace` stage.
+fn base_purl_cursor_filter(cursor: &BasePurlCursor) -> Condition {
+    let type_gt = Expr::col(base_purl::Column::Type).gt(cursor.r#type.clone());
+    let type_eq = Expr::col(base_purl::Column::Type).eq(cursor.r#type.clone());
+
+    let namespace_same = match &cursor.namespace {
+        Some(ns) => Expr::col(base_purl::Column::Namespace).eq(ns.clone()),
+        None => Expr::col(base_purl::Column::Namespace).is_null(),
+    };
+
+    let namespace_gt = match &cursor.namespace {
+        // NULLS LAST: a namespace sorts after `ns` if it's a strictly greater non-null value,
+        // or if it's NULL (NULL sorts after every non-null value).
+        Some(ns) => Condition::any()
+            .add(Expr::col(base_purl::Column::Namespace).gt(ns.clone()))
+            .add(Expr::col(base_purl::Column::Namespace).is_null()),
+        // The cursor's namespace is already NULL, i.e. already last among this `type`'s
+        // namespaces - nothing sorts after it without also advancing `type`.
+        None => Condition::any().add(Expr::value(false)),
+    };
+
+    let name_gt_same_namespace = Condition::all()
+        .add(namespace_same)
+        .add(Expr::col(base_purl::Column::Name).gt(cursor.name.clone()));
+
+    Condition::any().add(type_gt).add(
+        Condition::all().add(type_eq).add(
+            Condition::any()
+                .add(namespace_gt)
+                .add(name_gt_same_namespace),
+        ),
+    )
+}
+
+/// Encode a cursor as opaque base64, so callers can treat it as a token rather than parsing it
+fn encode_cursor<T: serde::Serialize>(value: &T) -> String {
+    let json = serde_json::to_vec(value).unwrap_or_default();
+    base64::engine::general_purpose::STANDARD.encode(json)
+}
+
+/// Decode a `base_purls_keyset` cursor. An invalid or malformed cursor is treated the same as
+/// no cursor at all (start from the beginning) rather than as an error, since it's opaque to
+/// callers and never constructed by hand.
+fn decode_base_purl_cursor(cursor: &str) -> Option<BasePurlCursor> {
+    let json = base64::engine::general_purpose::STANDARD
+        .decode(cursor)
+        .ok()?;
+    serde_json::from_slice(&json).ok()
+}
+
+fn encode_uuid_cursor(id: Uuid) -> String {
+    base64::engine::general_purpose::STANDARD.encode(id.as_bytes())
+}
+
+fn decode_uuid_cursor(cursor: &str) -> Option<Uuid> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(cursor)
+        .ok()?;
+    Uuid::from_slice(&bytes).ok()
+}
+
 #[cfg(test)]
 mod test;
@@ -0,0 +1,48 @@
+use super::*;
+use sea_query::PostgresQueryBuilder;
+
+fn render(cursor: &BasePurlCursor) -> String {
+    sea_query::Query::select()
+        .column(base_purl::Column::Id)
+        .from(base_purl::Entity)
+        .cond_where(base_purl_cursor_filter(cursor))
+        .to_string(PostgresQueryBuilder)
+}
+
+/// A cursor sitting on a NULL-namespace row must not silently exclude every later row of the
+/// same `type` that also has a NULL namespace: `namespace > NULL` is never true in plain SQL, so
+/// a naive row-wise tuple comparison drops them from all subsequent pages.
+#[test]
+fn null_namespace_cursor_does_not_drop_later_null_namespace_rows() {
+    let cursor = BasePurlCursor {
+        r#type: "generic".into(),
+        namespace: None,
+        name: "alpha".into(),
+    };
+
+    let sql = render(&cursor);
+
+    // the generated filter must not be a single tuple comparison involving the nullable
+    // namespace column directly against NULL, since that would always evaluate to unknown.
+    assert!(!sql.contains("(\"type\", \"namespace\", \"name\")"));
+    // the staged name comparison (type = cursor.type AND namespace IS NULL AND name > cursor.name)
+    // must still be reachable so rows sharing the NULL namespace keep paging correctly.
+    assert!(sql.contains("\"namespace\" IS NULL"));
+    assert!(sql.contains("\"name\" > "));
+}
+
+/// A cursor with a non-null namespace must still advance into rows whose namespace is NULL,
+/// since NULL sorts last under the `ORDER BY type, namespace, name` used to build the cursor.
+#[test]
+fn non_null_namespace_cursor_advances_into_null_namespace_rows() {
+    let cursor = BasePurlCursor {
+        r#type: "generic".into(),
+        namespace: Some("org.example".into()),
+        name: "alpha".into(),
+    };
+
+    let sql = render(&cursor);
+
+    assert!(sql.contains("\"namespace\" IS NULL"));
+    assert!(sql.contains("\"namespace\" > "));
+}
@@ -1,15 +1,36 @@
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 
-use reqwest::{Client, RequestBuilder, StatusCode};
+use rand::Rng;
+use reqwest::{Client, RequestBuilder, StatusCode, multipart};
 use thiserror::Error;
-use tokio::sync::RwLock;
+use tokio::task::JoinSet;
 use tokio::time::sleep;
 
-use crate::auth;
+/// Page size used by `get_all` when following a paginated list endpoint
+const PAGE_LIMIT: u32 = 100;
+
+/// The `{ items, total }` envelope returned by Trustify's paginated list endpoints
+#[derive(serde::Deserialize)]
+struct Page<T> {
+    items: Vec<T>,
+    total: u64,
+}
+
+/// Items gathered by `get_all` before a later page failed, paired with the error that ended
+/// pagination. Lets callers keep whatever was already fetched instead of discarding it on a
+/// transient server error.
+pub struct PartialResult<T> {
+    pub items: Vec<T>,
+    pub error: ApiError,
+}
+
+use crate::auth::AuthProvider;
 
 const MAX_RETRIES: u32 = 3;
-const RETRY_DELAY_MS: u64 = 1000;
+const BASE_RETRY_DELAY_MS: u64 = 1000;
+const MAX_RETRY_DELAY_MS: u64 = 30_000;
 
 #[derive(Error, Debug, Clone)]
 pub enum ApiError {
@@ -28,10 +49,36 @@ pub enum ApiError {
     #[error("Server timeout - please retry")]
     Timeout,
 
+    #[error("Rate limited by server (retry after {retry_after:?})")]
+    RateLimited { retry_after: Option<Duration> },
+
     #[error("Server error: {0}")]
     ServerError(String),
 }
 
+/// Parse a `Retry-After` header value, which is either a number of seconds (delta-seconds) or
+/// an HTTP-date naming the moment to retry at
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let target = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let delta = target.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    delta.to_std().ok()
+}
+
+/// Exponential backoff with full jitter: `random(0, min(cap, base * 2^attempt))`
+fn backoff_delay(attempt: u32) -> Duration {
+    let capped = BASE_RETRY_DELAY_MS
+        .saturating_mul(1u64 << attempt.min(16))
+        .min(MAX_RETRY_DELAY_MS);
+    let jittered = rand::rng().random_range(0..=capped);
+    Duration::from_millis(jittered)
+}
+
 impl From<reqwest::Error> for ApiError {
     fn from(e: reqwest::Error) -> Self {
         if e.is_timeout() {
@@ -42,29 +89,18 @@ impl From<reqwest::Error> for ApiError {
     }
 }
 
-/// Authentication credentials for token refresh
-#[derive(Clone)]
-pub struct AuthCredentials {
-    pub token_url: String,
-    pub client_id: String,
-    pub client_secret: String,
-}
-
 /// API client for Trustify with retry and token refresh support
 #[derive(Clone)]
 pub struct ApiClient {
     client: Client,
     base_url: String,
-    token: Arc<RwLock<Option<String>>>,
-    auth_credentials: Option<AuthCredentials>,
+    auth_provider: Option<Arc<dyn AuthProvider>>,
+    max_retries: u32,
+    retry_on_rate_limit: bool,
 }
 
 impl ApiClient {
-    pub fn new(
-        base_url: &str,
-        token: Option<String>,
-        auth_credentials: Option<AuthCredentials>,
-    ) -> Self {
+    pub fn new(base_url: &str, auth_provider: Option<Arc<dyn AuthProvider>>) -> Self {
         let base_url = base_url.trim_end_matches('/').to_string();
 
         Self {
@@ -73,38 +109,50 @@ impl ApiClient {
                 .build()
                 .unwrap_or_else(|_| Client::new()),
             base_url,
-            token: Arc::new(RwLock::new(token)),
-            auth_credentials,
+            auth_provider,
+            max_retries: MAX_RETRIES,
+            retry_on_rate_limit: true,
         }
     }
 
+    /// Override the number of attempts made for a retryable request (default 3)
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Control whether a `429 Too Many Requests` response is retried (default `true`)
+    pub fn with_retry_on_rate_limit(mut self, retry_on_rate_limit: bool) -> Self {
+        self.retry_on_rate_limit = retry_on_rate_limit;
+        self
+    }
+
     /// Build the full API URL
     pub fn url(&self, path: &str) -> String {
         format!("{}/api{}", self.base_url, path)
     }
 
-    /// Add authorization header if token is present
+    /// Add an authorization header if an auth provider is configured. The provider is
+    /// responsible for caching and proactively refreshing its own token.
     async fn authorize(&self, request: RequestBuilder) -> RequestBuilder {
-        let token = self.token.read().await;
-        match &*token {
-            Some(t) => request.bearer_auth(t),
-            None => request,
+        let Some(provider) = &self.auth_provider else {
+            return request;
+        };
+
+        match provider.token().await {
+            Ok(token) => request.bearer_auth(token),
+            Err(_) => request,
         }
     }
 
-    /// Refresh the token using stored credentials
+    /// Force the configured auth provider to refresh its token
     async fn refresh_token(&self) -> Result<(), ApiError> {
-        let creds = self
-            .auth_credentials
-            .as_ref()
-            .ok_or(ApiError::Unauthorized)?;
+        let provider = self.auth_provider.as_ref().ok_or(ApiError::Unauthorized)?;
 
         eprintln!("Token expired, refreshing...");
 
-        match auth::get_token(&creds.token_url, &creds.client_id, &creds.client_secret).await {
-            Ok(new_token) => {
-                let mut token = self.token.write().await;
-                *token = Some(new_token);
+        match provider.refresh().await {
+            Ok(_) => {
                 eprintln!("Token refreshed successfully");
                 Ok(())
             }
@@ -141,6 +189,107 @@ impl ApiClient {
         .await
     }
 
+    /// Follow every page of a paginated list endpoint and concatenate the results, keeping at
+    /// most one page in flight at a time.
+    pub async fn get_all<T>(
+        &self,
+        path: &str,
+        base_query: &impl serde::Serialize,
+    ) -> Result<Vec<T>, PartialResult<T>>
+    where
+        T: serde::de::DeserializeOwned + Send + 'static,
+    {
+        self.get_all_with_prefetch(path, base_query, 1).await
+    }
+
+    /// Like `get_all`, but issues up to `prefetch` page requests concurrently instead of
+    /// strictly sequentially. Pages are still appended in offset order, and a page beyond one
+    /// that returned an error is simply discarded since it was read-only.
+    pub async fn get_all_with_prefetch<T>(
+        &self,
+        path: &str,
+        base_query: &impl serde::Serialize,
+        prefetch: usize,
+    ) -> Result<Vec<T>, PartialResult<T>>
+    where
+        T: serde::de::DeserializeOwned + Send + 'static,
+    {
+        let prefetch = prefetch.max(1);
+        let base_value = serde_json::to_value(base_query).unwrap_or(serde_json::Value::Null);
+
+        let mut items = Vec::new();
+        let mut next_offset: u64 = 0;
+        let mut total: Option<u64> = None;
+
+        loop {
+            let mut set: JoinSet<(u64, Result<Page<T>, ApiError>)> = JoinSet::new();
+            let mut requested = 0usize;
+
+            for i in 0..prefetch {
+                let offset = next_offset + i as u64 * PAGE_LIMIT as u64;
+                if let Some(total) = total {
+                    if offset >= total {
+                        break;
+                    }
+                }
+
+                let mut query = base_value.clone();
+                if let serde_json::Value::Object(map) = &mut query {
+                    map.insert("limit".to_string(), PAGE_LIMIT.into());
+                    map.insert("offset".to_string(), offset.into());
+                }
+
+                let client = self.clone();
+                let path = path.to_string();
+                set.spawn(async move {
+                    let result = async {
+                        let body = client.get_with_query(&path, &query).await?;
+                        serde_json::from_str::<Page<T>>(&body)
+                            .map_err(|e| ApiError::RequestError(e.to_string()))
+                    }
+                    .await;
+                    (offset, result)
+                });
+                requested += 1;
+            }
+
+            if requested == 0 {
+                break;
+            }
+
+            let mut results = Vec::with_capacity(requested);
+            while let Some(joined) = set.join_next().await {
+                if let Ok(pair) = joined {
+                    results.push(pair);
+                }
+            }
+            results.sort_by_key(|(offset, _)| *offset);
+
+            let mut hit_short_page = false;
+            for (offset, result) in results {
+                match result {
+                    Ok(page) => {
+                        total = Some(page.total);
+                        let page_len = page.items.len() as u64;
+                        items.extend(page.items);
+                        next_offset = offset + page_len;
+                        if page_len < PAGE_LIMIT as u64 {
+                            hit_short_page = true;
+                            break;
+                        }
+                    }
+                    Err(error) => return Err(PartialResult { items, error }),
+                }
+            }
+
+            if hit_short_page || total.is_some_and(|total| next_offset >= total) {
+                break;
+            }
+        }
+
+        Ok(items)
+    }
+
     /// Perform a DELETE request with retry logic
     pub async fn delete(&self, path: &str) -> Result<String, ApiError> {
         self.execute_with_retry(|| async {
@@ -152,6 +301,91 @@ impl ApiClient {
         .await
     }
 
+    /// Serialize `body` as JSON and POST it
+    pub async fn post_json<T: serde::Serialize + Sync>(
+        &self,
+        path: &str,
+        body: &T,
+    ) -> Result<String, ApiError> {
+        let bytes =
+            serde_json::to_vec(body).map_err(|e| ApiError::RequestError(e.to_string()))?;
+        self.post_bytes(path, bytes, "application/json").await
+    }
+
+    /// Serialize `body` as JSON and PUT it
+    pub async fn put_json<T: serde::Serialize + Sync>(
+        &self,
+        path: &str,
+        body: &T,
+    ) -> Result<String, ApiError> {
+        let bytes =
+            serde_json::to_vec(body).map_err(|e| ApiError::RequestError(e.to_string()))?;
+        self.execute_with_retry(|| async {
+            let url = self.url(path);
+            let request = self
+                .client
+                .put(&url)
+                .header("Content-Type", "application/json")
+                .body(bytes.clone());
+            let response = self.authorize(request).await.send().await?;
+            self.handle_response(response).await
+        })
+        .await
+    }
+
+    /// POST an in-memory payload (e.g. a raw CycloneDX or SPDX document) with an explicit
+    /// content type, retrying by cloning the buffered body on each attempt
+    pub async fn post_bytes(
+        &self,
+        path: &str,
+        body: Vec<u8>,
+        content_type: &str,
+    ) -> Result<String, ApiError> {
+        self.execute_with_retry(|| async {
+            let url = self.url(path);
+            let request = self
+                .client
+                .post(&url)
+                .header("Content-Type", content_type)
+                .body(body.clone());
+            let response = self.authorize(request).await.send().await?;
+            self.handle_response(response).await
+        })
+        .await
+    }
+
+    /// Upload `file_path` as a `multipart/form-data` field named `field_name`.
+    ///
+    /// The file is re-opened on every retry attempt rather than buffered into memory, so large
+    /// SBOM documents can be streamed without blowing up memory.
+    pub async fn post_multipart(
+        &self,
+        path: &str,
+        field_name: &str,
+        file_path: &Path,
+    ) -> Result<String, ApiError> {
+        self.execute_with_retry(|| async {
+            let url = self.url(path);
+
+            let file_name = file_path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("upload")
+                .to_string();
+
+            let part = multipart::Part::file(file_path)
+                .await
+                .map_err(|e| ApiError::RequestError(e.to_string()))?
+                .file_name(file_name);
+            let form = multipart::Form::new().part(field_name.to_string(), part);
+
+            let request = self.client.post(&url).multipart(form);
+            let response = self.authorize(request).await.send().await?;
+            self.handle_response(response).await
+        })
+        .await
+    }
+
     /// Execute a request with retry logic for timeouts and token refresh
     async fn execute_with_retry<F, Fut>(&self, f: F) -> Result<String, ApiError>
     where
@@ -161,12 +395,12 @@ impl ApiClient {
         let mut last_error = ApiError::RequestError("No attempts made".to_string());
         let mut token_refreshed = false;
 
-        for attempt in 0..MAX_RETRIES {
+        for attempt in 0..self.max_retries {
             match f().await {
                 Ok(result) => return Ok(result),
                 Err(ApiError::TokenExpired) => {
                     if !token_refreshed
-                        && self.auth_credentials.is_some()
+                        && self.auth_provider.is_some()
                         && self.refresh_token().await.is_ok()
                     {
                         token_refreshed = true;
@@ -174,17 +408,30 @@ impl ApiClient {
                     }
                     return Err(ApiError::Unauthorized);
                 }
+                Err(ApiError::RateLimited { retry_after })
+                    if self.retry_on_rate_limit && attempt < self.max_retries - 1 =>
+                {
+                    let delay = retry_after.unwrap_or_else(|| backoff_delay(attempt));
+                    eprintln!(
+                        "Rate limited, retrying in {:?}... (attempt {}/{})",
+                        delay,
+                        attempt + 1,
+                        self.max_retries
+                    );
+                    sleep(delay).await;
+                    last_error = ApiError::RateLimited { retry_after };
+                }
                 Err(ApiError::Timeout) | Err(ApiError::ServerError(_))
-                    if attempt < MAX_RETRIES - 1 =>
+                    if attempt < self.max_retries - 1 =>
                 {
-                    let delay = RETRY_DELAY_MS * (attempt as u64 + 1);
+                    let delay = backoff_delay(attempt);
                     eprintln!(
-                        "Request failed, retrying in {}ms... (attempt {}/{})",
+                        "Request failed, retrying in {:?}... (attempt {}/{})",
                         delay,
                         attempt + 1,
-                        MAX_RETRIES
+                        self.max_retries
                     );
-                    sleep(Duration::from_millis(delay)).await;
+                    sleep(delay).await;
                     last_error = ApiError::Timeout;
                 }
                 Err(e) => return Err(e),
@@ -207,6 +454,13 @@ impl ApiClient {
             Err(ApiError::Unauthorized)
         } else if status == StatusCode::GATEWAY_TIMEOUT || status == StatusCode::REQUEST_TIMEOUT {
             Err(ApiError::Timeout)
+        } else if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(parse_retry_after);
+            Err(ApiError::RateLimited { retry_after })
         } else {
             let body = response.text().await.unwrap_or_default();
             Err(ApiError::ServerError(format!("HTTP {}: {}", status, body)))
@@ -0,0 +1,114 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// A token as persisted to disk, keyed by `token_url` + `client_id`
+#[derive(Serialize, Deserialize)]
+pub(super) struct PersistedToken {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    /// Absolute expiry as unix seconds, or `None` if the IdP didn't report a TTL
+    pub expires_at: Option<u64>,
+}
+
+/// The cache file for a given `token_url`/`client_id` pair, under
+/// `$XDG_CACHE_HOME/trustify/token-<hash>.json` (or the platform equivalent)
+fn cache_path(token_url: &str, client_id: &str) -> Option<PathBuf> {
+    let mut hasher = Sha256::new();
+    hasher.update(token_url.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(client_id.as_bytes());
+    let hash = format!("{:x}", hasher.finalize());
+
+    Some(
+        dirs::cache_dir()?
+            .join("trustify")
+            .join(format!("token-{hash}.json")),
+    )
+}
+
+/// Load a cached token for `token_url`/`client_id`, if the cache file exists, parses, and
+/// hasn't already expired. Any I/O or parse failure is treated as a cache miss since the cache
+/// is purely a speed optimization.
+pub(super) fn load(token_url: &str, client_id: &str) -> Option<PersistedToken> {
+    let path = cache_path(token_url, client_id)?;
+    let data = fs::read(path).ok()?;
+    let token: PersistedToken = serde_json::from_slice(&data).ok()?;
+
+    if let Some(expires_at) = token.expires_at {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+        if now >= expires_at {
+            return None;
+        }
+    }
+
+    Some(token)
+}
+
+/// Persist `token` for `token_url`/`client_id`, creating the cache directory if needed and
+/// writing the file pre-restricted to `0600` so the access/refresh tokens are never briefly
+/// world-readable. Failures are swallowed; a write error just means the next invocation
+/// re-authenticates.
+pub(super) fn save(token_url: &str, client_id: &str, token: &PersistedToken) {
+    let Some(path) = cache_path(token_url, client_id) else {
+        return;
+    };
+    let Some(parent) = path.parent() else {
+        return;
+    };
+    if fs::create_dir_all(parent).is_err() {
+        return;
+    }
+
+    let Ok(data) = serde_json::to_vec(token) else {
+        return;
+    };
+
+    write_private(&path, &data);
+}
+
+/// Write `data` to `path`, creating the file with `0600` permissions from the outset on unix
+/// (instead of `write` then `chmod`, which leaves a window where the file has default, often
+/// group/world-readable, permissions). Writes to a sibling temp file first and renames it into
+/// place, so a reader can never observe a partially written file at `path` either.
+fn write_private(path: &std::path::Path, data: &[u8]) {
+    let Some(parent) = path.parent() else {
+        return;
+    };
+    let tmp_path = parent.join(format!(".{}.tmp", std::process::id()));
+
+    #[cfg(unix)]
+    let opened = {
+        use std::os::unix::fs::OpenOptionsExt;
+        fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(&tmp_path)
+    };
+    #[cfg(not(unix))]
+    let opened = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&tmp_path);
+
+    let Ok(mut file) = opened else {
+        return;
+    };
+
+    use std::io::Write;
+    if file.write_all(data).is_err() {
+        let _ = fs::remove_file(&tmp_path);
+        return;
+    }
+    drop(file);
+
+    if fs::rename(&tmp_path, path).is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+}
@@ -0,0 +1,143 @@
+mod cache;
+pub mod provider;
+
+use std::time::Duration;
+
+use reqwest::{Client, Response};
+use serde::Deserialize;
+use thiserror::Error;
+
+pub use provider::AuthProvider;
+
+#[derive(Error, Debug)]
+pub enum AuthError {
+    #[error("Failed to connect to SSO server: {0}")]
+    ConnectionError(#[from] reqwest::Error),
+
+    #[error("Authentication failed: Invalid client_id, client_secret, or SSO URL. Please verify your credentials.")]
+    AuthenticationFailed,
+
+    #[error("SSO server returned an error: {0}")]
+    ServerError(String),
+}
+
+/// An access token along with how long it remains valid for, and a refresh token if the
+/// SSO server issued one
+pub struct Token {
+    pub access_token: String,
+    pub expires_in: Option<Duration>,
+    pub refresh_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[allow(dead_code)]
+    token_type: String,
+    expires_in: Option<u64>,
+    refresh_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: String,
+    error_description: Option<String>,
+}
+
+/// Retrieves an OAuth2 access token using client credentials grant
+pub async fn get_token(
+    token_url: &str,
+    client_id: &str,
+    client_secret: &str,
+) -> Result<Token, AuthError> {
+    let client = Client::new();
+
+    let response = client
+        .post(token_url)
+        .form(&[
+            ("grant_type", "client_credentials"),
+            ("client_id", client_id),
+            ("client_secret", client_secret),
+        ])
+        .send()
+        .await?;
+
+    parse_token_response(response).await
+}
+
+/// Retrieves an OAuth2 access token using the resource-owner password credentials grant
+pub async fn get_token_password(
+    token_url: &str,
+    client_id: &str,
+    client_secret: Option<&str>,
+    username: &str,
+    password: &str,
+) -> Result<Token, AuthError> {
+    let client = Client::new();
+
+    let mut form = vec![
+        ("grant_type", "password"),
+        ("client_id", client_id),
+        ("username", username),
+        ("password", password),
+    ];
+    if let Some(secret) = client_secret {
+        form.push(("client_secret", secret));
+    }
+
+    let response = client.post(token_url).form(&form).send().await?;
+
+    parse_token_response(response).await
+}
+
+/// Retrieves a new access token using a previously issued refresh token, avoiding re-sending
+/// the client secret on every renewal
+pub async fn refresh_with_token(
+    token_url: &str,
+    client_id: &str,
+    refresh_token: &str,
+) -> Result<Token, AuthError> {
+    let client = Client::new();
+
+    let response = client
+        .post(token_url)
+        .form(&[
+            ("grant_type", "refresh_token"),
+            ("client_id", client_id),
+            ("refresh_token", refresh_token),
+        ])
+        .send()
+        .await?;
+
+    parse_token_response(response).await
+}
+
+/// Parse a token endpoint response shared by the various grant helpers above
+async fn parse_token_response(response: Response) -> Result<Token, AuthError> {
+    if response.status().is_success() {
+        let token_response: TokenResponse = response.json().await?;
+        Ok(Token {
+            access_token: token_response.access_token,
+            expires_in: token_response.expires_in.map(Duration::from_secs),
+            refresh_token: token_response.refresh_token,
+        })
+    } else if response.status().as_u16() == 401 || response.status().as_u16() == 400 {
+        // Try to get error details
+        if let Ok(error_response) = response.json::<ErrorResponse>().await {
+            if error_response.error == "invalid_client"
+                || error_response.error == "unauthorized_client"
+            {
+                return Err(AuthError::AuthenticationFailed);
+            }
+            let msg = error_response
+                .error_description
+                .unwrap_or(error_response.error);
+            return Err(AuthError::ServerError(msg));
+        }
+        Err(AuthError::AuthenticationFailed)
+    } else {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        Err(AuthError::ServerError(format!("HTTP {}: {}", status, body)))
+    }
+}
@@ -0,0 +1,257 @@
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+
+use super::cache::{self, PersistedToken};
+use super::{AuthError, Token, get_token, get_token_password, refresh_with_token};
+
+/// Tokens cached as longer-lived than this are assumed to have no real expiry and are not
+/// persisted with an `expires_at`, mirroring the `u32::MAX`-seconds sentinel `CachedToken`
+/// uses in memory for the same case
+const LONG_LIVED_THRESHOLD: Duration = Duration::from_secs(60 * 60 * 24 * 365);
+
+/// Safety margin subtracted from a token's lifetime before we consider it expired
+const TOKEN_EXPIRY_SKEW: Duration = Duration::from_secs(30);
+
+/// A pluggable source of bearer tokens for `ApiClient`.
+///
+/// Implementations are responsible for their own caching and refresh strategy; `ApiClient`
+/// simply asks for a token before each request and calls `refresh` when a request comes back
+/// `401` despite a token that looked valid.
+#[async_trait]
+pub trait AuthProvider: Send + Sync {
+    /// Return a token to use for the next request, reusing a cached one if still valid.
+    async fn token(&self) -> Result<String, AuthError>;
+
+    /// Force a refresh, bypassing any cache, and return the new token.
+    async fn refresh(&self) -> Result<String, AuthError>;
+}
+
+/// A token cached alongside the instant at which it should be considered expired
+struct CachedToken {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_on: Instant,
+}
+
+impl CachedToken {
+    fn from_token(token: Token) -> Self {
+        let expires_on = match token.expires_in {
+            Some(ttl) => Instant::now() + ttl.saturating_sub(TOKEN_EXPIRY_SKEW),
+            None => Instant::now() + Duration::from_secs(u32::MAX as u64),
+        };
+        Self {
+            access_token: token.access_token,
+            refresh_token: token.refresh_token,
+            expires_on,
+        }
+    }
+
+    fn is_expired(&self) -> bool {
+        Instant::now() >= self.expires_on
+    }
+
+    /// Reconstruct a cached token from its on-disk form, converting the persisted absolute
+    /// unix expiry back into an `Instant` relative to now
+    fn from_persisted(persisted: PersistedToken) -> Self {
+        let expires_on = match persisted.expires_at {
+            Some(unix_secs) => {
+                let now_unix = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                let remaining = Duration::from_secs(unix_secs.saturating_sub(now_unix));
+                Instant::now() + remaining
+            }
+            None => Instant::now() + Duration::from_secs(u32::MAX as u64),
+        };
+        Self {
+            access_token: persisted.access_token,
+            refresh_token: persisted.refresh_token,
+            expires_on,
+        }
+    }
+
+    /// Convert to the on-disk form, turning the `Instant`-based expiry into an absolute unix
+    /// timestamp so it survives a process restart
+    fn to_persisted(&self) -> PersistedToken {
+        let remaining = self.expires_on.saturating_duration_since(Instant::now());
+        let expires_at = if remaining > LONG_LIVED_THRESHOLD {
+            None
+        } else {
+            let now_unix = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            Some(now_unix + remaining.as_secs())
+        };
+
+        PersistedToken {
+            access_token: self.access_token.clone(),
+            refresh_token: self.refresh_token.clone(),
+            expires_at,
+        }
+    }
+}
+
+/// OAuth2 client-credentials grant, the original (and still default) flow
+pub struct ClientCredentialsProvider {
+    token_url: String,
+    client_id: String,
+    client_secret: String,
+    cached: RwLock<Option<CachedToken>>,
+}
+
+impl ClientCredentialsProvider {
+    pub fn new(token_url: impl Into<String>, client_id: impl Into<String>, client_secret: impl Into<String>) -> Self {
+        let token_url = token_url.into();
+        let client_id = client_id.into();
+
+        // Reuse a still-valid token from a previous invocation instead of re-authenticating
+        // against the IdP on every CLI command.
+        let cached = cache::load(&token_url, &client_id).map(CachedToken::from_persisted);
+
+        Self {
+            token_url,
+            client_id,
+            client_secret: client_secret.into(),
+            cached: RwLock::new(cached),
+        }
+    }
+
+    /// Cache `token` in memory and persist it to disk so later invocations can reuse it
+    async fn cache_token(&self, token: Token) -> String {
+        let access_token = token.access_token.clone();
+        let cached = CachedToken::from_token(token);
+        cache::save(&self.token_url, &self.client_id, &cached.to_persisted());
+        *self.cached.write().await = Some(cached);
+        access_token
+    }
+
+    /// Fetch a brand-new token via the client credentials grant
+    async fn fetch_initial(&self) -> Result<String, AuthError> {
+        let token = get_token(&self.token_url, &self.client_id, &self.client_secret).await?;
+        Ok(self.cache_token(token).await)
+    }
+
+    /// Renew the current token, preferring the refresh-token grant (which avoids resending the
+    /// client secret) and falling back to client credentials if no refresh token is on hand or
+    /// the SSO server rejects it.
+    async fn fetch(&self) -> Result<String, AuthError> {
+        let refresh_token = self
+            .cached
+            .read()
+            .await
+            .as_ref()
+            .and_then(|c| c.refresh_token.clone());
+
+        if let Some(refresh_token) = refresh_token {
+            if let Ok(token) = refresh_with_token(&self.token_url, &self.client_id, &refresh_token).await {
+                return Ok(self.cache_token(token).await);
+            }
+        }
+
+        self.fetch_initial().await
+    }
+}
+
+#[async_trait]
+impl AuthProvider for ClientCredentialsProvider {
+    async fn token(&self) -> Result<String, AuthError> {
+        if let Some(cached) = self.cached.read().await.as_ref() {
+            if !cached.is_expired() {
+                return Ok(cached.access_token.clone());
+            }
+        }
+        self.fetch().await
+    }
+
+    async fn refresh(&self) -> Result<String, AuthError> {
+        self.fetch().await
+    }
+}
+
+/// OAuth2 resource-owner password credentials grant
+pub struct PasswordProvider {
+    token_url: String,
+    client_id: String,
+    client_secret: Option<String>,
+    username: String,
+    password: String,
+    cached: RwLock<Option<CachedToken>>,
+}
+
+impl PasswordProvider {
+    pub fn new(
+        token_url: impl Into<String>,
+        client_id: impl Into<String>,
+        client_secret: Option<String>,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        Self {
+            token_url: token_url.into(),
+            client_id: client_id.into(),
+            client_secret,
+            username: username.into(),
+            password: password.into(),
+            cached: RwLock::new(None),
+        }
+    }
+
+    async fn fetch(&self) -> Result<String, AuthError> {
+        let token = get_token_password(
+            &self.token_url,
+            &self.client_id,
+            self.client_secret.as_deref(),
+            &self.username,
+            &self.password,
+        )
+        .await?;
+        let access_token = token.access_token.clone();
+        *self.cached.write().await = Some(CachedToken::from_token(token));
+        Ok(access_token)
+    }
+}
+
+#[async_trait]
+impl AuthProvider for PasswordProvider {
+    async fn token(&self) -> Result<String, AuthError> {
+        if let Some(cached) = self.cached.read().await.as_ref() {
+            if !cached.is_expired() {
+                return Ok(cached.access_token.clone());
+            }
+        }
+        self.fetch().await
+    }
+
+    async fn refresh(&self) -> Result<String, AuthError> {
+        self.fetch().await
+    }
+}
+
+/// A static, long-lived bearer token with no refresh capability (e.g. a service account token
+/// issued out-of-band). `refresh` just returns the same token.
+pub struct StaticBearerProvider {
+    token: String,
+}
+
+impl StaticBearerProvider {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl AuthProvider for StaticBearerProvider {
+    async fn token(&self) -> Result<String, AuthError> {
+        Ok(self.token.clone())
+    }
+
+    async fn refresh(&self) -> Result<String, AuthError> {
+        Ok(self.token.clone())
+    }
+}
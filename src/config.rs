@@ -1,5 +1,11 @@
+use std::sync::Arc;
+
 use clap::Args;
 
+use crate::api::auth::build_token_url;
+use crate::auth::AuthProvider;
+use crate::auth::provider::{ClientCredentialsProvider, PasswordProvider, StaticBearerProvider};
+
 /// Configuration for connecting to Trustify API
 #[derive(Args, Clone)]
 pub struct Config {
@@ -18,6 +24,18 @@ pub struct Config {
     /// OAuth2 Client Secret
     #[arg(long = "client-secret", env = "TRUSTIFY_CLIENT_SECRET")]
     pub client_secret: Option<String>,
+
+    /// A static, long-lived bearer token to use instead of an OAuth2 grant
+    #[arg(long = "bearer-token", env = "TRUSTIFY_BEARER_TOKEN")]
+    pub bearer_token: Option<String>,
+
+    /// Username for the OAuth2 resource-owner password grant
+    #[arg(long = "username", env = "TRUSTIFY_USERNAME")]
+    pub username: Option<String>,
+
+    /// Password for the OAuth2 resource-owner password grant
+    #[arg(long = "password", env = "TRUSTIFY_PASSWORD")]
+    pub password: Option<String>,
 }
 
 impl Config {
@@ -33,4 +51,41 @@ impl Config {
             _ => None,
         }
     }
+
+    /// Returns the username/password credentials if both are present
+    pub fn password_credentials(&self) -> Option<(&str, &str)> {
+        match (&self.username, &self.password) {
+            (Some(username), Some(password)) => Some((username.as_str(), password.as_str())),
+            _ => None,
+        }
+    }
+
+    /// Build an [`AuthProvider`] from whichever credentials are configured, preferring the
+    /// client-credentials grant, then a static bearer token, then the resource-owner password
+    /// grant - the precedence `main` used to apply inline. Returns `None` if no credentials are
+    /// configured at all, in which case `ApiClient` makes unauthenticated requests.
+    pub fn into_auth_provider(&self) -> Option<Arc<dyn AuthProvider>> {
+        if let Some((sso_url, client_id, client_secret)) = self.auth_credentials() {
+            let token_url = build_token_url(sso_url);
+            Some(Arc::new(ClientCredentialsProvider::new(
+                token_url,
+                client_id,
+                client_secret,
+            )))
+        } else if let Some(bearer_token) = &self.bearer_token {
+            Some(Arc::new(StaticBearerProvider::new(bearer_token.clone())))
+        } else if let Some((username, password)) = self.password_credentials() {
+            let sso_url = self.sso_url.as_deref().unwrap_or_default();
+            let token_url = build_token_url(sso_url);
+            Some(Arc::new(PasswordProvider::new(
+                token_url,
+                self.client_id.clone().unwrap_or_default(),
+                self.client_secret.clone(),
+                username,
+                password,
+            )))
+        } else {
+            None
+        }
+    }
 }
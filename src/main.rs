@@ -8,7 +8,6 @@ use std::process;
 
 use clap::Parser;
 
-use api::client::AuthCredentials;
 use api::ApiClient;
 use cli::Cli;
 
@@ -25,37 +24,19 @@ async fn main() {
 
     let cli = Cli::parse();
 
-    // Build auth credentials and get initial token if configured
-    let (token, auth_credentials) =
-        if let Some((sso_url, client_id, client_secret)) = cli.config.auth_credentials() {
-            let token_url = if sso_url.ends_with("/token") {
-                sso_url.to_string()
-            } else if sso_url.ends_with('/') {
-                format!("{}protocol/openid-connect/token", sso_url)
-            } else {
-                format!("{}/protocol/openid-connect/token", sso_url)
-            };
+    let auth_provider = cli.config.into_auth_provider();
 
-            // Store credentials for token refresh
-            let creds = AuthCredentials {
-                token_url: token_url.clone(),
-                client_id: client_id.to_string(),
-                client_secret: client_secret.to_string(),
-            };
+    // Eagerly validate credentials up front so configuration errors surface immediately
+    // instead of on the first API call.
+    if let Some(provider) = &auth_provider {
+        if let Err(e) = provider.token().await {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    }
 
-            match auth::get_token(&token_url, client_id, client_secret).await {
-                Ok(token) => (Some(token), Some(creds)),
-                Err(e) => {
-                    eprintln!("Error: {}", e);
-                    process::exit(1);
-                }
-            }
-        } else {
-            (None, None)
-        };
-
-    // Create API client with auth credentials for token refresh
-    let client = ApiClient::new(&cli.config.url, token, auth_credentials);
+    // Create API client with the selected auth provider
+    let client = ApiClient::new(&cli.config.url, auth_provider);
 
     let ctx = Context {
         config: cli.config,
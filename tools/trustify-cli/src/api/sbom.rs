@@ -1,21 +1,176 @@
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufReader, Write};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader, Read, Write};
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::sync::atomic::{AtomicU32, Ordering};
 
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use futures::future::join_all;
 use futures::stream::{self, StreamExt};
-use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use tokio::sync::Mutex;
+use tar::{Archive, Builder as TarBuilder, Header as TarHeader};
+use tokio::sync::{Mutex, RwLock};
+use uuid::Uuid;
 
 use super::client::{ApiClient, ApiError};
 
 const SBOM_PATH: &str = "/v2/sbom";
 
+/// Broad category of a [`SbomApiError`], for programmatic dispatch without string-matching
+/// `message`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiErrorType {
+    /// The server's response couldn't be parsed or was missing expected fields
+    InvalidResponse,
+    /// A local file read/write failed
+    Io,
+    /// The requested resource doesn't exist
+    NotFound,
+    /// Anything else
+    Internal,
+}
+
+/// Structured, machine-readable error for the sbom API module: a stable `error_code` for
+/// programmatic handling, a broad `error_type` category, a human-readable `message`, and an
+/// optional `link` to documentation describing the failure. Replaces the former
+/// `ApiError::InternalError(String)` call sites in this module, which collapsed every failure
+/// into an opaque string.
+#[derive(Debug, Clone, Serialize)]
+pub struct SbomApiError {
+    pub error_code: &'static str,
+    pub error_type: ApiErrorType,
+    pub message: String,
+    pub link: Option<&'static str>,
+}
+
+impl SbomApiError {
+    fn new(error_code: &'static str, error_type: ApiErrorType, message: impl Into<String>) -> Self {
+        Self {
+            error_code,
+            error_type,
+            message: message.into(),
+            link: None,
+        }
+    }
+}
+
+impl std::fmt::Display for SbomApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({:?}): {}",
+            self.error_code, self.error_type, self.message
+        )
+    }
+}
+
+impl std::error::Error for SbomApiError {}
+
+impl From<ApiError> for SbomApiError {
+    fn from(e: ApiError) -> Self {
+        match &e {
+            ApiError::NotFound(_) => {
+                Self::new("sbom_not_found", ApiErrorType::NotFound, e.to_string())
+            }
+            _ => Self::new(
+                "sbom_api_request_failed",
+                ApiErrorType::Internal,
+                e.to_string(),
+            ),
+        }
+    }
+}
+
+/// Default number of attempts made for a retryable operation before its failure is recorded
+const DEFAULT_MAX_RETRY_ATTEMPTS: u32 = 4;
+
+const RETRY_BASE_DELAY_MS: u64 = 250;
+const RETRY_MAX_DELAY_MS: u64 = 10_000;
+
+/// Whether a failure is worth retrying. A `NotFound` is definitive - retrying an operation on a
+/// resource that doesn't exist can't produce a different outcome - everything else this module
+/// sees from `ApiClient` (connection errors, timeouts, 5xx, 429s) is folded into `Internal` and
+/// is worth another attempt.
+fn is_retryable(error: &SbomApiError) -> bool {
+    error.error_type != ApiErrorType::NotFound
+}
+
+/// Exponential backoff with full jitter: `random(0, min(cap, base * 2^attempt))`
+fn retry_backoff(attempt: u32) -> std::time::Duration {
+    let capped = RETRY_BASE_DELAY_MS
+        .saturating_mul(1u64 << attempt.min(16))
+        .min(RETRY_MAX_DELAY_MS);
+    let jittered = rand::rng().random_range(0..=capped);
+    std::time::Duration::from_millis(jittered)
+}
+
+/// Retry `operation` up to `max_attempts` times with exponential backoff and jitter between
+/// attempts, giving up immediately on a non-retryable error (e.g. `NotFound`). `task` is credited
+/// with a retry each time an attempt is reattempted.
+async fn with_retry<T, F, Fut>(
+    task: &TaskHandle,
+    max_attempts: u32,
+    mut operation: F,
+) -> Result<T, SbomApiError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, SbomApiError>>,
+{
+    let mut attempt = 0;
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt + 1 < max_attempts && is_retryable(&e) => {
+                task.inc_retries(1).await;
+                tokio::time::sleep(retry_backoff(attempt)).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Client-side requests-per-second ceiling shared across concurrent workers, so a large scan or
+/// deletion sweep doesn't trip the server's own rate limiting in the first place. Approximates a
+/// token bucket of depth 1 by reserving evenly-spaced time slots: each `acquire()` call claims the
+/// next free slot and sleeps until it arrives.
+struct RateLimiter {
+    interval: std::time::Duration,
+    next_slot: Mutex<tokio::time::Instant>,
+}
+
+impl RateLimiter {
+    fn new(requests_per_second: f64) -> Self {
+        let interval = std::time::Duration::from_secs_f64(1.0 / requests_per_second.max(0.001));
+        Self {
+            interval,
+            next_slot: Mutex::new(tokio::time::Instant::now()),
+        }
+    }
+
+    /// Wait until the next available slot, reporting whether this call had to wait at all
+    async fn acquire(&self) -> bool {
+        let mut slot = self.next_slot.lock().await;
+        let now = tokio::time::Instant::now();
+        let wait_until = if *slot > now { *slot } else { now };
+        *slot = wait_until + self.interval;
+        drop(slot);
+
+        let throttled = wait_until > now;
+        tokio::time::sleep_until(wait_until).await;
+        throttled
+    }
+}
+
 /// Query parameters for listing SBOMs
 #[derive(Default, Serialize)]
 pub struct ListParams {
@@ -33,16 +188,336 @@ pub struct ListParams {
 pub struct FindDuplicatesParams {
     pub batch_size: u32,
     pub concurrency: usize,
+    /// Path to a prior scan's NDJSON checkpoint journal to resume from. Pages already recorded
+    /// there are not re-fetched; the final grouping pass runs over the journaled entries plus
+    /// whatever is newly fetched. If `None`, a fresh journal is still written (so a later scan
+    /// can resume from it) at `"{output}.journal.ndjson"`.
+    pub resume_from: Option<String>,
+    /// When set, group SBOMs whose component/purl sets are near-duplicates (estimated Jaccard
+    /// similarity at or above this threshold) instead of requiring a byte-identical
+    /// `document_id`. `None` keeps the default exact-`document_id` grouping.
+    pub similarity_threshold: Option<f64>,
+    /// Optional ceiling on requests per second shared across all fetch workers, so a large scan
+    /// doesn't trip the server's own rate limiting. `None` means unlimited.
+    pub requests_per_second: Option<f64>,
 }
 
 /// SBOM entry for duplicate detection
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct SbomEntry {
     id: String,
     document_id: String,
     published: Option<String>,
 }
 
+/// One page's worth of checkpoint data, as a single NDJSON line keyed by `offset`
+#[derive(Serialize, Deserialize)]
+struct JournalRecord {
+    offset: u32,
+    entries: Vec<SbomEntry>,
+}
+
+/// Reload a checkpoint journal, returning the offsets already fetched and their entries. A
+/// missing file means there's nothing to resume from yet, not an error. Earlier records win on
+/// a duplicate offset, so a partial/interrupted append never double-counts a page.
+fn load_journal(path: &str) -> Result<(HashSet<u32>, Vec<SbomEntry>), SbomApiError> {
+    let file = match File::open(path) {
+        Ok(f) => f,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok((HashSet::new(), Vec::new()))
+        }
+        Err(e) => {
+            return Err(SbomApiError::new(
+                "checkpoint_open_failed",
+                ApiErrorType::Io,
+                format!("Failed to open checkpoint journal: {}", e),
+            ));
+        }
+    };
+
+    let mut offsets = HashSet::new();
+    let mut entries = Vec::new();
+
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|e| {
+            SbomApiError::new(
+                "checkpoint_read_failed",
+                ApiErrorType::Io,
+                format!("Failed to read checkpoint journal: {}", e),
+            )
+        })?;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let record: JournalRecord = serde_json::from_str(&line).map_err(|e| {
+            SbomApiError::new(
+                "checkpoint_parse_failed",
+                ApiErrorType::InvalidResponse,
+                format!("Failed to parse checkpoint journal line: {}", e),
+            )
+        })?;
+
+        if offsets.insert(record.offset) {
+            entries.extend(record.entries);
+        }
+    }
+
+    Ok((offsets, entries))
+}
+
+/// Open (or create) the checkpoint journal for appending newly-fetched pages
+fn open_journal_for_append(path: &str) -> Result<File, SbomApiError> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| {
+            SbomApiError::new(
+                "checkpoint_open_failed",
+                ApiErrorType::Io,
+                format!("Failed to open checkpoint journal for writing: {}", e),
+            )
+        })
+}
+
+/// Append one page's entries to the checkpoint journal, flushing so a crash immediately after
+/// doesn't lose the record
+async fn append_journal_record(
+    journal: &Mutex<File>,
+    offset: u32,
+    entries: &[SbomEntry],
+) -> Result<(), SbomApiError> {
+    let record = JournalRecord {
+        offset,
+        entries: entries.to_vec(),
+    };
+    let line = serde_json::to_string(&record).map_err(|e| {
+        SbomApiError::new(
+            "checkpoint_serialize_failed",
+            ApiErrorType::Internal,
+            format!("Failed to serialize checkpoint record: {}", e),
+        )
+    })?;
+
+    let mut file = journal.lock().await;
+    writeln!(file, "{}", line).map_err(|e| {
+        SbomApiError::new(
+            "checkpoint_write_failed",
+            ApiErrorType::Io,
+            format!("Failed to append checkpoint record: {}", e),
+        )
+    })?;
+    file.flush().map_err(|e| {
+        SbomApiError::new(
+            "checkpoint_write_failed",
+            ApiErrorType::Io,
+            format!("Failed to flush checkpoint journal: {}", e),
+        )
+    })
+}
+
+/// Number of independent hash functions in a MinHash signature
+const MINHASH_K: usize = 32;
+/// Number of LSH bands the signature is split into; must evenly divide `MINHASH_K`
+const LSH_BANDS: usize = 8;
+/// Rows per LSH band
+const LSH_ROWS: usize = MINHASH_K / LSH_BANDS;
+
+/// Fixed-length MinHash signature over an SBOM's component/purl set
+type MinHashSignature = [u64; MINHASH_K];
+
+/// Compute a MinHash signature for a set of component identifiers: for each of the
+/// `MINHASH_K` independent hash functions (one hash function salted `MINHASH_K` different
+/// ways), keep the minimum hash seen across all identifiers. Two sets whose signatures agree in
+/// many positions are estimated to have high Jaccard similarity, without ever materializing the
+/// full pairwise intersection/union.
+fn minhash_signature(components: &[String]) -> MinHashSignature {
+    let mut signature = [u64::MAX; MINHASH_K];
+    for component in components {
+        for (salt, slot) in signature.iter_mut().enumerate() {
+            let mut hasher = DefaultHasher::new();
+            salt.hash(&mut hasher);
+            component.hash(&mut hasher);
+            let h = hasher.finish();
+            if h < *slot {
+                *slot = h;
+            }
+        }
+    }
+    signature
+}
+
+/// Estimate Jaccard similarity between two signatures as the fraction of matching positions
+fn estimate_similarity(a: &MinHashSignature, b: &MinHashSignature) -> f64 {
+    let matches = a.iter().zip(b.iter()).filter(|(x, y)| x == y).count();
+    matches as f64 / MINHASH_K as f64
+}
+
+/// LSH bucket keys for a signature: split into `LSH_BANDS` bands of `LSH_ROWS` rows each and
+/// hash every band, so two signatures only need comparing if they collide in at least one band.
+fn lsh_buckets(signature: &MinHashSignature) -> Vec<(usize, u64)> {
+    signature
+        .chunks(LSH_ROWS)
+        .enumerate()
+        .map(|(band, rows)| {
+            let mut hasher = DefaultHasher::new();
+            rows.hash(&mut hasher);
+            (band, hasher.finish())
+        })
+        .collect()
+}
+
+/// Find the root of `x`'s set, compressing the path as it goes
+fn uf_find(parents: &mut [usize], x: usize) -> usize {
+    if parents[x] != x {
+        parents[x] = uf_find(parents, parents[x]);
+    }
+    parents[x]
+}
+
+/// Fetch the component purls for a single SBOM, retrying retryable failures with backoff and
+/// honoring `rate_limiter` the same way `fetch_worker` does for the exact-duplicate scan
+async fn fetch_components_with_retry(
+    client: &ApiClient,
+    id: &str,
+    task: &TaskHandle,
+    rate_limiter: &Option<Arc<RateLimiter>>,
+) -> Result<Vec<String>, SbomApiError> {
+    if let Some(limiter) = rate_limiter {
+        if limiter.acquire().await {
+            task.inc_throttled(1).await;
+        }
+    }
+
+    with_retry(task, DEFAULT_MAX_RETRY_ATTEMPTS, || fetch_components(client, id)).await
+}
+
+/// Fetch the component purls for a single SBOM
+async fn fetch_components(client: &ApiClient, id: &str) -> Result<Vec<String>, SbomApiError> {
+    let path = format!("{}/{}/packages", SBOM_PATH, id);
+    let response = client.get(&path).await?;
+
+    let parsed: Value = serde_json::from_str(&response).map_err(|e| {
+        SbomApiError::new(
+            "sbom_packages_parse_failed",
+            ApiErrorType::InvalidResponse,
+            format!("Failed to parse packages response: {}", e),
+        )
+    })?;
+
+    let items = parsed
+        .get("items")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| {
+            SbomApiError::new(
+                "missing_items_field",
+                ApiErrorType::InvalidResponse,
+                "No items in packages response",
+            )
+        })?;
+
+    Ok(items
+        .iter()
+        .filter_map(|item| {
+            item.get("purl")
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        })
+        .collect())
+}
+
+/// Group SBOMs whose component/purl sets are estimated to be near-duplicates (Jaccard
+/// similarity >= `threshold`) via MinHash + LSH, instead of requiring a byte-identical
+/// `document_id`. Reuses the same published-descending tiebreak as exact grouping to pick the
+/// canonical `id` for each cluster.
+async fn find_near_duplicate_groups(
+    client: &ApiClient,
+    entries: Vec<SbomEntry>,
+    threshold: f64,
+    concurrency: usize,
+    task: &TaskHandle,
+    rate_limiter: &Option<Arc<RateLimiter>>,
+) -> Vec<DuplicateGroup> {
+    let signatures: Vec<(SbomEntry, MinHashSignature)> = stream::iter(entries)
+        .map(|entry| {
+            let client = client.clone();
+            async move {
+                let components =
+                    fetch_components_with_retry(&client, &entry.id, task, rate_limiter)
+                        .await
+                        .unwrap_or_else(|e| {
+                            log::error!("Failed to fetch components for {}: {}", entry.id, e);
+                            Vec::new()
+                        });
+                let signature = minhash_signature(&components);
+                (entry, signature)
+            }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await;
+
+    // Bucket by LSH band so only SBOMs that collide in at least one band get compared
+    let mut buckets: HashMap<(usize, u64), Vec<usize>> = HashMap::new();
+    for (i, (_, signature)) in signatures.iter().enumerate() {
+        for bucket in lsh_buckets(signature) {
+            buckets.entry(bucket).or_default().push(i);
+        }
+    }
+
+    let mut parents: Vec<usize> = (0..signatures.len()).collect();
+    for members in buckets.values() {
+        for i in 0..members.len() {
+            for j in (i + 1)..members.len() {
+                let (a, b) = (members[i], members[j]);
+                if estimate_similarity(&signatures[a].1, &signatures[b].1) >= threshold {
+                    let (ra, rb) = (uf_find(&mut parents, a), uf_find(&mut parents, b));
+                    if ra != rb {
+                        parents[ra] = rb;
+                    }
+                }
+            }
+        }
+    }
+
+    let mut clusters: HashMap<usize, Vec<usize>> = HashMap::new();
+    for i in 0..signatures.len() {
+        let root = uf_find(&mut parents, i);
+        clusters.entry(root).or_default().push(i);
+    }
+
+    let mut groups = Vec::new();
+    for members in clusters.values() {
+        if members.len() <= 1 {
+            continue;
+        }
+
+        let mut cluster_entries: Vec<SbomEntry> =
+            members.iter().map(|&i| signatures[i].0.clone()).collect();
+
+        cluster_entries.sort_by(|a, b| match (&b.published, &a.published) {
+            (Some(b_pub), Some(a_pub)) => b_pub.cmp(a_pub),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+
+        let most_recent = cluster_entries.remove(0);
+        let duplicates: Vec<String> = cluster_entries.into_iter().map(|e| e.id).collect();
+
+        groups.push(DuplicateGroup {
+            document_id: most_recent.document_id,
+            published: most_recent.published,
+            id: most_recent.id,
+            duplicates,
+        });
+    }
+
+    groups
+}
+
 /// Duplicate group output format
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DuplicateGroup {
@@ -68,7 +543,7 @@ async fn fetch_page(
     client: &ApiClient,
     batch_size: u32,
     offset: u32,
-) -> Result<Vec<SbomEntry>, ApiError> {
+) -> Result<Vec<SbomEntry>, SbomApiError> {
     let list_params = ListParams {
         q: None,
         limit: Some(batch_size),
@@ -77,13 +552,24 @@ async fn fetch_page(
     };
 
     let response = list(client, &list_params).await?;
-    let parsed: Value = serde_json::from_str(&response)
-        .map_err(|e| ApiError::InternalError(format!("Failed to parse response: {}", e)))?;
+    let parsed: Value = serde_json::from_str(&response).map_err(|e| {
+        SbomApiError::new(
+            "sbom_list_parse_failed",
+            ApiErrorType::InvalidResponse,
+            format!("Failed to parse response: {}", e),
+        )
+    })?;
 
     let items = parsed
         .get("items")
         .and_then(|v| v.as_array())
-        .ok_or_else(|| ApiError::InternalError("No items in response".to_string()))?;
+        .ok_or_else(|| {
+            SbomApiError::new(
+                "missing_items_field",
+                ApiErrorType::InvalidResponse,
+                "No items in response",
+            )
+        })?;
 
     let entries: Vec<SbomEntry> = items
         .iter()
@@ -113,45 +599,104 @@ async fn fetch_page(
     Ok(entries)
 }
 
-/// Worker that fetches assigned pages sequentially
+/// Worker that fetches assigned pages sequentially, reporting progress on `task` and stopping
+/// early if the task is cancelled. Retryable failures (anything but a definitive not-found) are
+/// retried with backoff before a page is given up on.
 async fn fetch_worker(
     worker_id: usize,
     client: ApiClient,
     pages: Vec<u32>,
     batch_size: u32,
-    progress_bar: ProgressBar,
+    task: TaskHandle,
     results: Arc<Mutex<Vec<SbomEntry>>>,
+    journal: Arc<Mutex<File>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
 ) {
-    let mut fetched: u64 = 0;
-
     for offset in pages {
-        match fetch_page(&client, batch_size, offset).await {
+        if task.is_cancelled() {
+            break;
+        }
+
+        if let Some(limiter) = &rate_limiter {
+            if limiter.acquire().await {
+                task.inc_throttled(1).await;
+            }
+        }
+
+        let fetch_result = with_retry(&task, DEFAULT_MAX_RETRY_ATTEMPTS, || {
+            fetch_page(&client, batch_size, offset)
+        })
+        .await;
+
+        match fetch_result {
             Ok(entries) => {
-                fetched += entries.len() as u64;
-                progress_bar.set_position(fetched);
+                if let Err(e) = append_journal_record(&journal, offset, &entries).await {
+                    log::error!(
+                        "Worker {}: failed to checkpoint offset {}: {}",
+                        worker_id,
+                        offset,
+                        e
+                    );
+                }
+                task.inc_processed(entries.len() as u64).await;
                 results.lock().await.extend(entries);
             }
             Err(e) => {
-                progress_bar.println(format!(
-                    "Worker {}: Error at offset {}: {}",
-                    worker_id, offset, e
-                ));
+                log::error!("Worker {}: Error at offset {}: {}", worker_id, offset, e);
             }
         }
     }
+}
 
-    progress_bar.finish_with_message("done");
+/// Run the duplicate scan to completion, reporting progress on `task` as pages complete
+async fn run_find_duplicates(
+    client: ApiClient,
+    params: FindDuplicatesParams,
+    output_file: Option<String>,
+    task: TaskHandle,
+) {
+    let result = find_duplicates_inner(&client, &params, &output_file, &task).await;
+    match result {
+        Ok(groups) => task.complete(TaskResult::FindDuplicates { groups }).await,
+        Err(e) => task.fail(e.to_string()).await,
+    }
 }
 
-/// Find duplicate SBOMs by document_id and save to file
-pub async fn find_duplicates(
+async fn find_duplicates_inner(
     client: &ApiClient,
     params: &FindDuplicatesParams,
     output_file: &Option<String>,
-) -> Result<Vec<DuplicateGroup>, ApiError> {
+    task: &TaskHandle,
+) -> Result<Vec<DuplicateGroup>, SbomApiError> {
     let batch_size = params.batch_size;
     let concurrency = params.concurrency;
 
+    let output_path = output_file
+        .as_ref()
+        .map(|s| s.as_str())
+        .unwrap_or("duplicates.json");
+    let journal_path = params
+        .resume_from
+        .clone()
+        .unwrap_or_else(|| format!("{}.journal.ndjson", output_path));
+
+    // A fresh scan discards any journal an earlier scan left at the default path
+    if params.resume_from.is_none() {
+        if let Err(e) = std::fs::remove_file(&journal_path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                return Err(SbomApiError::new(
+                    "checkpoint_reset_failed",
+                    ApiErrorType::Io,
+                    format!("Failed to discard stale checkpoint journal: {}", e),
+                ));
+            }
+        }
+    }
+
+    // Reload whatever a prior (possibly interrupted) scan already checkpointed, so we never
+    // re-fetch a page that already succeeded.
+    let (journaled_offsets, journaled_entries) = load_journal(&journal_path)?;
+
     // First, get the total count
     let first_page = list(
         client,
@@ -164,49 +709,42 @@ pub async fn find_duplicates(
     )
     .await?;
 
-    let parsed: Value = serde_json::from_str(&first_page)
-        .map_err(|e| ApiError::InternalError(format!("Failed to parse response: {}", e)))?;
+    let parsed: Value = serde_json::from_str(&first_page).map_err(|e| {
+        SbomApiError::new(
+            "sbom_list_parse_failed",
+            ApiErrorType::InvalidResponse,
+            format!("Failed to parse response: {}", e),
+        )
+    })?;
 
     let total = parsed.get("total").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
 
+    task.set_total(total as u64).await;
+
     if total == 0 {
-        eprintln!("No SBOMs found");
         return Ok(Vec::new());
     }
 
-    eprintln!("Fetching {} SBOMs with {} workers...\n", total, concurrency);
-
-    // Calculate page offsets
+    // Calculate page offsets, skipping whatever the checkpoint journal already covers
     let num_pages = total.div_ceil(batch_size);
-    let all_offsets: Vec<u32> = (0..num_pages).map(|i| i * batch_size).collect();
+    let remaining_offsets: Vec<u32> = (0..num_pages)
+        .map(|i| i * batch_size)
+        .filter(|offset| !journaled_offsets.contains(offset))
+        .collect();
 
-    // Distribute pages evenly among workers
+    task.inc_processed(journaled_entries.len() as u64).await;
+
+    // Distribute the remaining pages evenly among workers
     let mut worker_pages: Vec<Vec<u32>> = vec![Vec::new(); concurrency];
-    for (i, offset) in all_offsets.into_iter().enumerate() {
+    for (i, offset) in remaining_offsets.into_iter().enumerate() {
         worker_pages[i % concurrency].push(offset);
     }
 
-    // Calculate how many SBOMs each worker will fetch
-    let worker_counts: Vec<u64> = worker_pages
-        .iter()
-        .map(|pages| {
-            pages
-                .iter()
-                .map(|&offset| {
-                    let remaining = total.saturating_sub(offset);
-                    remaining.min(batch_size) as u64
-                })
-                .sum()
-        })
-        .collect();
-
-    // Set up progress bars
-    let multi_progress = MultiProgress::new();
-    let style = ProgressStyle::default_bar()
-        .template("{prefix:>12} [{bar:30.cyan/blue}] {pos}/{len} ({percent}%)")?
-        .progress_chars("█▓░");
-
-    let results: Arc<Mutex<Vec<SbomEntry>>> = Arc::new(Mutex::new(Vec::new()));
+    let results: Arc<Mutex<Vec<SbomEntry>>> = Arc::new(Mutex::new(journaled_entries));
+    let journal = Arc::new(Mutex::new(open_journal_for_append(&journal_path)?));
+    let rate_limiter = params
+        .requests_per_second
+        .map(|rps| Arc::new(RateLimiter::new(rps)));
 
     // Spawn workers
     let mut handles = Vec::new();
@@ -215,21 +753,21 @@ pub async fn find_duplicates(
             continue;
         }
 
-        let worker_total = worker_counts[worker_id];
-        let pb = multi_progress.add(ProgressBar::new(worker_total));
-        pb.set_style(style.clone());
-        pb.set_prefix(format!("Worker {}", worker_id + 1));
-
         let client = client.clone();
         let results = Arc::clone(&results);
+        let task = task.clone();
+        let journal = Arc::clone(&journal);
+        let rate_limiter = rate_limiter.clone();
 
         handles.push(tokio::spawn(fetch_worker(
             worker_id + 1,
             client,
             pages,
             batch_size,
-            pb,
+            task,
             results,
+            journal,
+            rate_limiter,
         )));
     }
 
@@ -237,83 +775,659 @@ pub async fn find_duplicates(
     join_all(handles).await;
 
     let all_entries = Arc::try_unwrap(results)
-        .map_err(|_| ApiError::InternalError("Failed to unwrap entries".to_string()))?
+        .map_err(|_| {
+            SbomApiError::new(
+                "entries_lock_held",
+                ApiErrorType::Internal,
+                "Failed to unwrap entries: a worker task is still holding a reference",
+            )
+        })?
         .into_inner();
 
-    eprintln!("\nProcessing {} SBOMs for duplicates...", all_entries.len());
+    let duplicate_groups: Vec<DuplicateGroup> = match params.similarity_threshold {
+        Some(threshold) => {
+            find_near_duplicate_groups(
+                client,
+                all_entries,
+                threshold,
+                concurrency,
+                task,
+                &rate_limiter,
+            )
+            .await
+        }
+        None => {
+            // Group by document_id
+            let mut groups: HashMap<String, Vec<SbomEntry>> = HashMap::new();
+            for entry in all_entries {
+                groups
+                    .entry(entry.document_id.clone())
+                    .or_default()
+                    .push(entry);
+            }
 
-    // Group by document_id
-    let mut groups: HashMap<String, Vec<SbomEntry>> = HashMap::new();
-    for entry in all_entries {
-        groups
-            .entry(entry.document_id.clone())
-            .or_default()
-            .push(entry);
-    }
+            // Find duplicates (groups with more than one entry)
+            let mut duplicate_groups: Vec<DuplicateGroup> = Vec::new();
 
-    // Find duplicates (groups with more than one entry)
-    let mut duplicate_groups: Vec<DuplicateGroup> = Vec::new();
+            for (document_id, mut entries) in groups {
+                if entries.len() <= 1 {
+                    continue;
+                }
 
-    for (document_id, mut entries) in groups {
-        if entries.len() <= 1 {
-            continue;
+                // Sort by published date descending (most recent first)
+                entries.sort_by(|a, b| match (&b.published, &a.published) {
+                    (Some(b_pub), Some(a_pub)) => b_pub.cmp(a_pub),
+                    (Some(_), None) => std::cmp::Ordering::Less,
+                    (None, Some(_)) => std::cmp::Ordering::Greater,
+                    (None, None) => std::cmp::Ordering::Equal,
+                });
+
+                let most_recent = entries.remove(0);
+                let duplicates: Vec<String> = entries.into_iter().map(|e| e.id).collect();
+
+                duplicate_groups.push(DuplicateGroup {
+                    document_id,
+                    published: most_recent.published,
+                    id: most_recent.id,
+                    duplicates,
+                });
+            }
+
+            duplicate_groups
         }
+    };
 
-        // Sort by published date descending (most recent first)
-        entries.sort_by(|a, b| match (&b.published, &a.published) {
-            (Some(b_pub), Some(a_pub)) => b_pub.cmp(a_pub),
-            (Some(_), None) => std::cmp::Ordering::Less,
-            (None, Some(_)) => std::cmp::Ordering::Greater,
-            (None, None) => std::cmp::Ordering::Equal,
-        });
+    // Save to file
+    let json = serde_json::to_string_pretty(&duplicate_groups).map_err(|e| {
+        SbomApiError::new(
+            "output_serialize_failed",
+            ApiErrorType::Internal,
+            format!("Failed to serialize results: {}", e),
+        )
+    })?;
 
-        let most_recent = entries.remove(0);
-        let duplicates: Vec<String> = entries.into_iter().map(|e| e.id).collect();
+    let mut file = File::create(output_path).map_err(|e| {
+        SbomApiError::new(
+            "output_create_failed",
+            ApiErrorType::Io,
+            format!("Failed to create output file: {}", e),
+        )
+    })?;
 
-        duplicate_groups.push(DuplicateGroup {
-            document_id,
-            published: most_recent.published,
-            id: most_recent.id,
-            duplicates,
+    file.write_all(json.as_bytes()).map_err(|e| {
+        SbomApiError::new(
+            "output_write_failed",
+            ApiErrorType::Io,
+            format!("Failed to write to file: {}", e),
+        )
+    })?;
+
+    Ok(duplicate_groups)
+}
+
+/// Register a background task that scans for duplicate SBOMs by `document_id` (or, when
+/// `params.similarity_threshold` is set, by near-duplicate component sets) and returns
+/// immediately with its task ID. Poll `registry.get_task(id)` for progress and the final
+/// `Vec<DuplicateGroup>` result.
+pub async fn find_duplicates(
+    client: &ApiClient,
+    params: FindDuplicatesParams,
+    output_file: Option<String>,
+    registry: &TaskRegistry,
+) -> TaskId {
+    let client = client.clone();
+    let task = registry.register().await;
+    let task_id = task.id().to_string();
+
+    tokio::spawn(run_find_duplicates(client, params, output_file, task));
+
+    task_id
+}
+
+/// Delete an SBOM by ID
+pub async fn delete(client: &ApiClient, id: &str) -> Result<(), SbomApiError> {
+    let path = format!("{}/{}", SBOM_PATH, id);
+    client.delete(&path).await?;
+    Ok(())
+}
+
+/// Page size used when resolving the IDs matched by a `--query` delete
+const DELETE_QUERY_PAGE_SIZE: u32 = 100;
+
+/// Outcome of a `delete_by_query` sweep
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteResult {
+    pub deleted: u32,
+    pub skipped: u32,
+    pub failed: u32,
+    pub total: u32,
+}
+
+/// Page through the list endpoint collecting every SBOM ID matching `query` (or every SBOM, if
+/// `query` is `None`)
+async fn collect_matching_ids(
+    client: &ApiClient,
+    query: Option<&str>,
+) -> Result<Vec<String>, SbomApiError> {
+    let mut ids = Vec::new();
+    let mut offset = 0u32;
+
+    loop {
+        let params = ListParams {
+            q: query.map(|s| s.to_string()),
+            limit: Some(DELETE_QUERY_PAGE_SIZE),
+            offset: Some(offset),
+            sort: None,
+        };
+
+        let response = list(client, &params).await?;
+        let parsed: Value = serde_json::from_str(&response).map_err(|e| {
+            SbomApiError::new(
+                "sbom_list_parse_failed",
+                ApiErrorType::InvalidResponse,
+                format!("Failed to parse response: {}", e),
+            )
+        })?;
+        let items = parsed.get("items").and_then(|v| v.as_array()).ok_or_else(|| {
+            SbomApiError::new(
+                "missing_items_field",
+                ApiErrorType::InvalidResponse,
+                "No items in response",
+            )
+        })?;
+
+        if items.is_empty() {
+            break;
+        }
+
+        for item in items {
+            if let Some(id) = item.get("id").and_then(|v| v.as_str()) {
+                ids.push(id.to_string());
+            }
+        }
+
+        let total = parsed.get("total").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
+        offset += items.len() as u32;
+        if offset >= total || (items.len() as u32) < DELETE_QUERY_PAGE_SIZE {
+            break;
+        }
+    }
+
+    Ok(ids)
+}
+
+/// Delete a single SBOM by `id`, or every SBOM matching `query` (resolved by paging through the
+/// list endpoint), with up to `concurrency` deletes in flight at once. A `NotFound` response is
+/// counted as skipped rather than failed, since the SBOM is gone either way.
+pub async fn delete_by_query(
+    client: &ApiClient,
+    query: Option<&str>,
+    dry_run: bool,
+    concurrency: usize,
+    id: Option<&str>,
+) -> Result<DeleteResult, SbomApiError> {
+    let ids: Vec<String> = match id {
+        Some(id) => vec![id.to_string()],
+        None => collect_matching_ids(client, query).await?,
+    };
+
+    let total = ids.len() as u32;
+
+    if dry_run {
+        return Ok(DeleteResult {
+            deleted: 0,
+            skipped: 0,
+            failed: 0,
+            total,
         });
     }
 
-    eprintln!(
-        "Found {} document(s) with duplicates",
-        duplicate_groups.len()
+    let counts: Vec<(u32, u32, u32)> = stream::iter(ids)
+        .map(|id| {
+            let client = client.clone();
+            async move {
+                match delete(&client, &id).await {
+                    Ok(()) => (1, 0, 0),
+                    Err(e) if e.error_type == ApiErrorType::NotFound => (0, 1, 0),
+                    Err(e) => {
+                        log::error!("Failed to delete {}: {}", id, e);
+                        (0, 0, 1)
+                    }
+                }
+            }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await;
+
+    let (deleted, skipped, failed) = counts.into_iter().fold(
+        (0u32, 0u32, 0u32),
+        |(deleted, skipped, failed), (d, s, f)| (deleted + d, skipped + s, failed + f),
     );
 
-    // Save to file
-    let output_path = output_file
-        .as_ref()
-        .map(|s| s.as_str())
-        .unwrap_or("duplicates.json");
+    Ok(DeleteResult {
+        deleted,
+        skipped,
+        failed,
+        total,
+    })
+}
 
-    let json = serde_json::to_string_pretty(&duplicate_groups)
-        .map_err(|e| ApiError::InternalError(format!("Failed to serialize results: {}", e)))?;
+/// Outcome of exporting SBOMs to disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportResult {
+    pub exported: u32,
+    pub failed: u32,
+    pub total: u32,
+}
 
-    let mut file = File::create(output_path)
-        .map_err(|e| ApiError::InternalError(format!("Failed to create output file: {}", e)))?;
+/// Fetch the full document for every id in `ids`, with up to `concurrency` requests in flight at
+/// once. Each result is paired with the id that produced it so a failure can be attributed.
+async fn fetch_documents(
+    client: &ApiClient,
+    ids: Vec<String>,
+    concurrency: usize,
+) -> Vec<(String, Result<String, SbomApiError>)> {
+    stream::iter(ids)
+        .map(|id| {
+            let client = client.clone();
+            async move {
+                let result = get(&client, &id).await.map_err(SbomApiError::from);
+                (id, result)
+            }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await
+}
 
-    file.write_all(json.as_bytes())
-        .map_err(|e| ApiError::InternalError(format!("Failed to write to file: {}", e)))?;
+/// The `document_id` embedded in a fetched SBOM document, falling back to its `id` if the field
+/// is missing or the document can't be parsed
+fn document_id_of(id: &str, json: &str) -> String {
+    serde_json::from_str::<Value>(json)
+        .ok()
+        .and_then(|v| {
+            v.get("document_id")
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| id.to_string())
+}
 
-    Ok(duplicate_groups)
+/// The filename (without extension) to export a document under: `document_id` if it's a safe
+/// single path segment, or `id` otherwise. `document_id` comes from the server and routinely
+/// contains characters like `/` (it's often a URL); used unsanitized, it would let a document
+/// escape `output_dir` via `../` on export, or become a path-traversal ("zip-slip") entry in the
+/// archive when later extracted with an ordinary tar tool.
+fn export_filename(id: &str, document_id: &str) -> String {
+    let is_safe_segment = !document_id.is_empty()
+        && document_id != "."
+        && document_id != ".."
+        && !document_id.contains('/')
+        && !document_id.contains('\\');
+
+    if is_safe_segment {
+        document_id.to_string()
+    } else {
+        id.to_string()
+    }
 }
 
-/// Delete an SBOM by ID
-pub async fn delete(client: &ApiClient, id: &str) -> Result<(), ApiError> {
-    let path = format!("{}/{}", SBOM_PATH, id);
-    client.delete(&path).await?;
+/// Export a single SBOM by `id`, or every SBOM matching `query`, to `output_path`. When `archive`
+/// is set, `output_path` is written as a single gzipped tar containing one `{document_id}.json`
+/// entry per document (`{id}.json` when `document_id` isn't a safe path segment, see
+/// `export_filename`); otherwise `output_path` is treated as a directory and one file per
+/// document is written into it.
+pub async fn export_sboms(
+    client: &ApiClient,
+    query: Option<&str>,
+    id: Option<&str>,
+    output_path: &str,
+    archive: bool,
+    concurrency: usize,
+) -> Result<ExportResult, SbomApiError> {
+    let ids: Vec<String> = match id {
+        Some(id) => vec![id.to_string()],
+        None => collect_matching_ids(client, query).await?,
+    };
+    let total = ids.len() as u32;
+
+    let documents = fetch_documents(client, ids, concurrency).await;
+
+    if archive {
+        export_to_archive(output_path, documents)
+    } else {
+        export_to_directory(output_path, documents)
+    }
+}
+
+fn export_to_archive(
+    output_path: &str,
+    documents: Vec<(String, Result<String, SbomApiError>)>,
+) -> Result<ExportResult, SbomApiError> {
+    let file = File::create(output_path).map_err(|e| {
+        SbomApiError::new(
+            "export_archive_create_failed",
+            ApiErrorType::Io,
+            format!("Failed to create archive: {}", e),
+        )
+    })?;
+    let mut builder = TarBuilder::new(GzEncoder::new(file, Compression::default()));
+
+    let mut exported = 0;
+    let mut failed = 0;
+    let total = documents.len() as u32;
+
+    for (id, result) in documents {
+        match result {
+            Ok(json) => {
+                let document_id = document_id_of(&id, &json);
+                let filename = export_filename(&id, &document_id);
+                let mut header = TarHeader::new_gnu();
+                header.set_size(json.len() as u64);
+                header.set_mode(0o644);
+                header.set_cksum();
+                builder
+                    .append_data(&mut header, format!("{}.json", filename), json.as_bytes())
+                    .map_err(|e| {
+                        SbomApiError::new(
+                            "export_archive_write_failed",
+                            ApiErrorType::Io,
+                            format!("Failed to write {} to archive: {}", id, e),
+                        )
+                    })?;
+                exported += 1;
+            }
+            Err(e) => {
+                log::error!("Failed to export {}: {}", id, e);
+                failed += 1;
+            }
+        }
+    }
+
+    builder.finish().map_err(|e| {
+        SbomApiError::new(
+            "export_archive_finish_failed",
+            ApiErrorType::Io,
+            format!("Failed to finalize archive: {}", e),
+        )
+    })?;
+
+    Ok(ExportResult {
+        exported,
+        failed,
+        total,
+    })
+}
+
+fn export_to_directory(
+    output_dir: &str,
+    documents: Vec<(String, Result<String, SbomApiError>)>,
+) -> Result<ExportResult, SbomApiError> {
+    std::fs::create_dir_all(output_dir).map_err(|e| {
+        SbomApiError::new(
+            "export_dir_create_failed",
+            ApiErrorType::Io,
+            format!("Failed to create output directory: {}", e),
+        )
+    })?;
+
+    let mut exported = 0;
+    let mut failed = 0;
+    let total = documents.len() as u32;
+
+    for (id, result) in documents {
+        match result {
+            Ok(json) => {
+                let document_id = document_id_of(&id, &json);
+                let filename = export_filename(&id, &document_id);
+                let path = Path::new(output_dir).join(format!("{}.json", filename));
+                match File::create(&path).and_then(|mut f| f.write_all(json.as_bytes())) {
+                    Ok(()) => exported += 1,
+                    Err(e) => {
+                        log::error!("Failed to write {} to {}: {}", id, path.display(), e);
+                        failed += 1;
+                    }
+                }
+            }
+            Err(e) => {
+                log::error!("Failed to export {}: {}", id, e);
+                failed += 1;
+            }
+        }
+    }
+
+    Ok(ExportResult {
+        exported,
+        failed,
+        total,
+    })
+}
+
+/// Per-file outcome of an `import_sboms` sweep
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportOutcome {
+    pub name: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// Outcome of importing SBOMs from disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportResult {
+    pub outcomes: Vec<ImportOutcome>,
+    pub imported: u32,
+    pub failed: u32,
+    pub total: u32,
+}
+
+/// Read every document to import from `input`, which is either a directory of documents or a
+/// gzipped tar archive (as written by `export_sboms`)
+fn collect_import_files(input: &str) -> Result<Vec<(String, Vec<u8>)>, SbomApiError> {
+    let path = Path::new(input);
+    if !path.exists() {
+        return Err(SbomApiError::new(
+            "input_path_not_found",
+            ApiErrorType::Io,
+            format!("Input path not found: {}", input),
+        ));
+    }
+
+    if path.is_dir() {
+        let mut files = Vec::new();
+        let entries = std::fs::read_dir(path).map_err(|e| {
+            SbomApiError::new(
+                "input_dir_read_failed",
+                ApiErrorType::Io,
+                format!("Failed to read input directory: {}", e),
+            )
+        })?;
+        for entry in entries {
+            let entry = entry.map_err(|e| {
+                SbomApiError::new(
+                    "input_dir_read_failed",
+                    ApiErrorType::Io,
+                    format!("Failed to read directory entry: {}", e),
+                )
+            })?;
+            let entry_path = entry.path();
+            if !entry_path.is_file() {
+                continue;
+            }
+            let name = entry_path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("unknown")
+                .to_string();
+            let bytes = std::fs::read(&entry_path).map_err(|e| {
+                SbomApiError::new(
+                    "input_file_read_failed",
+                    ApiErrorType::Io,
+                    format!("Failed to read {}: {}", entry_path.display(), e),
+                )
+            })?;
+            files.push((name, bytes));
+        }
+        Ok(files)
+    } else {
+        let file = File::open(path).map_err(|e| {
+            SbomApiError::new(
+                "archive_open_failed",
+                ApiErrorType::Io,
+                format!("Failed to open archive: {}", e),
+            )
+        })?;
+        let mut archive = Archive::new(GzDecoder::new(file));
+        let entries = archive.entries().map_err(|e| {
+            SbomApiError::new(
+                "archive_read_failed",
+                ApiErrorType::Io,
+                format!("Failed to read archive: {}", e),
+            )
+        })?;
+
+        let mut files = Vec::new();
+        for entry in entries {
+            let mut entry = entry.map_err(|e| {
+                SbomApiError::new(
+                    "archive_read_failed",
+                    ApiErrorType::Io,
+                    format!("Failed to read archive entry: {}", e),
+                )
+            })?;
+            let name = entry
+                .path()
+                .map_err(|e| {
+                    SbomApiError::new(
+                        "archive_read_failed",
+                        ApiErrorType::Io,
+                        format!("Failed to read archive entry path: {}", e),
+                    )
+                })?
+                .to_string_lossy()
+                .into_owned();
+            let mut bytes = Vec::new();
+            entry.read_to_end(&mut bytes).map_err(|e| {
+                SbomApiError::new(
+                    "archive_read_failed",
+                    ApiErrorType::Io,
+                    format!("Failed to read archive entry bytes: {}", e),
+                )
+            })?;
+            files.push((name, bytes));
+        }
+        Ok(files)
+    }
+}
+
+/// Upload one document's raw bytes to the ingest endpoint
+async fn ingest_document(client: &ApiClient, bytes: Vec<u8>) -> Result<(), SbomApiError> {
+    client
+        .post_bytes(SBOM_PATH, bytes, "application/json")
+        .await?;
     Ok(())
 }
 
+/// Import every document found under `input` (a directory, or a gzipped tar archive), uploading
+/// up to `concurrency` at once
+pub async fn import_sboms(
+    client: &ApiClient,
+    input: &str,
+    concurrency: usize,
+) -> Result<ImportResult, SbomApiError> {
+    let files = collect_import_files(input)?;
+    let total = files.len() as u32;
+
+    let outcomes: Vec<ImportOutcome> = stream::iter(files)
+        .map(|(name, bytes)| {
+            let client = client.clone();
+            async move {
+                match ingest_document(&client, bytes).await {
+                    Ok(()) => ImportOutcome {
+                        name,
+                        success: true,
+                        error: None,
+                    },
+                    Err(e) => {
+                        log::error!("Failed to import {}: {}", name, e);
+                        ImportOutcome {
+                            name,
+                            success: false,
+                            error: Some(e.to_string()),
+                        }
+                    }
+                }
+            }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await;
+
+    let imported = outcomes.iter().filter(|o| o.success).count() as u32;
+    let failed = total - imported;
+
+    Ok(ImportResult {
+        outcomes,
+        imported,
+        failed,
+        total,
+    })
+}
+
+/// Default number of IDs submitted per `delete_batch` request
+pub const DEFAULT_DELETE_BATCH_SIZE: u32 = 50;
+
+/// Per-ID outcome reported by the batch delete endpoint
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum BatchDeleteStatus {
+    Deleted,
+    NotFound,
+    Error,
+}
+
+/// One entry of a `delete_batch` response
+#[derive(Debug, Clone, Deserialize)]
+struct BatchDeleteEntry {
+    id: String,
+    status: BatchDeleteStatus,
+    message: Option<String>,
+}
+
+/// Delete many SBOMs in one request. POSTs `ids` as a JSON array to the batch-delete endpoint
+/// and returns the per-ID outcome, so a large duplicate set can be deleted in `ids.len() /
+/// batch_size` round trips instead of one DELETE per ID.
+async fn delete_batch(
+    client: &ApiClient,
+    ids: &[String],
+) -> Result<Vec<BatchDeleteEntry>, SbomApiError> {
+    let path = format!("{}/batch-delete", SBOM_PATH);
+    let response = client.post_json(&path, &ids).await?;
+
+    serde_json::from_str(&response).map_err(|e| {
+        SbomApiError::new(
+            "batch_delete_parse_failed",
+            ApiErrorType::InvalidResponse,
+            format!("Failed to parse batch delete response: {}", e),
+        )
+    })
+}
+
 /// Result of deleting duplicates
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeleteDuplicatesResult {
     pub deleted: u32,
     pub skipped: u32,
     pub failed: u32,
     pub total: u32,
+    /// Number of batch-delete requests retried after a retryable failure
+    pub retries: u32,
+    /// Number of batch-delete requests delayed by the client-side rate limiter
+    pub throttled: u32,
+    /// Number of entries already recorded as done in a resumed run's progress journal, and so
+    /// not resubmitted. Included in `deleted`/`skipped`/`failed` above, broken out here for
+    /// visibility into how much of the run picked up where a prior attempt left off.
+    pub resumed: u32,
 }
 
 /// Entry to delete with its document_id for logging
@@ -323,29 +1437,204 @@ struct DeleteEntry {
     document_id: String,
 }
 
-/// Delete duplicates from a file with progress bar
-pub async fn delete_duplicates(
+/// Outcome of a single duplicate-deletion attempt, as recorded in the progress journal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum DeleteOutcome {
+    Deleted,
+    Skipped,
+    Failed,
+}
+
+/// One completed deletion, as a single journal line
+#[derive(Serialize, Deserialize)]
+struct DeleteJournalRecord {
+    id: String,
+    outcome: DeleteOutcome,
+}
+
+/// Derive the progress journal path for a `duplicates.json`-style input file, e.g.
+/// `duplicates.json` -> `duplicates.journal.jsonl`
+fn delete_journal_path(input_file: &str) -> String {
+    let path = Path::new(input_file);
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("duplicates");
+    let parent = path.parent().and_then(|p| p.to_str()).unwrap_or("");
+
+    if parent.is_empty() {
+        format!("{}.journal.jsonl", stem)
+    } else {
+        format!("{}/{}.journal.jsonl", parent, stem)
+    }
+}
+
+/// Reload a deletion progress journal, returning the outcome already recorded for each id. A
+/// missing file means there's nothing to resume from yet, not an error. Later records win on a
+/// duplicate id, matching how an interrupted run's most recent attempt would be the correct one.
+fn load_delete_journal(path: &str) -> Result<HashMap<String, DeleteOutcome>, SbomApiError> {
+    let file = match File::open(path) {
+        Ok(f) => f,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(e) => {
+            return Err(SbomApiError::new(
+                "journal_open_failed",
+                ApiErrorType::Io,
+                format!("Failed to open deletion journal: {}", e),
+            ));
+        }
+    };
+
+    let mut outcomes = HashMap::new();
+
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|e| {
+            SbomApiError::new(
+                "journal_read_failed",
+                ApiErrorType::Io,
+                format!("Failed to read deletion journal: {}", e),
+            )
+        })?;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let record: DeleteJournalRecord = serde_json::from_str(&line).map_err(|e| {
+            SbomApiError::new(
+                "journal_parse_failed",
+                ApiErrorType::InvalidResponse,
+                format!("Failed to parse deletion journal line: {}", e),
+            )
+        })?;
+
+        outcomes.insert(record.id, record.outcome);
+    }
+
+    Ok(outcomes)
+}
+
+/// Open the deletion progress journal for appending. When `resume` is false, any existing
+/// journal is truncated so the run starts from a clean slate.
+fn open_delete_journal(path: &str, resume: bool) -> Result<File, SbomApiError> {
+    let mut options = OpenOptions::new();
+    options.create(true);
+    if resume {
+        options.append(true);
+    } else {
+        options.write(true).truncate(true);
+    }
+    options.open(path).map_err(|e| {
+        SbomApiError::new(
+            "journal_open_failed",
+            ApiErrorType::Io,
+            format!("Failed to open deletion journal for writing: {}", e),
+        )
+    })
+}
+
+/// Append one completed deletion to the journal, flushing so a crash immediately after doesn't
+/// lose the record
+async fn append_delete_journal_record(
+    journal: &Mutex<File>,
+    id: &str,
+    outcome: DeleteOutcome,
+) -> Result<(), SbomApiError> {
+    let record = DeleteJournalRecord {
+        id: id.to_string(),
+        outcome,
+    };
+    let line = serde_json::to_string(&record).map_err(|e| {
+        SbomApiError::new(
+            "journal_serialize_failed",
+            ApiErrorType::Internal,
+            format!("Failed to serialize journal record: {}", e),
+        )
+    })?;
+
+    let mut file = journal.lock().await;
+    writeln!(file, "{}", line).map_err(|e| {
+        SbomApiError::new(
+            "journal_write_failed",
+            ApiErrorType::Io,
+            format!("Failed to append journal record: {}", e),
+        )
+    })?;
+    file.flush().map_err(|e| {
+        SbomApiError::new(
+            "journal_write_failed",
+            ApiErrorType::Io,
+            format!("Failed to flush deletion journal: {}", e),
+        )
+    })
+}
+
+/// Run the deletion sweep to completion, reporting progress on `task` as entries complete
+async fn run_delete_duplicates(
+    client: ApiClient,
+    input_file: String,
+    batch_size: u32,
+    concurrency: usize,
+    dry_run: bool,
+    requests_per_second: Option<f64>,
+    resume: bool,
+    task: TaskHandle,
+) {
+    let result = delete_duplicates_inner(
+        &client,
+        &input_file,
+        batch_size,
+        concurrency,
+        dry_run,
+        requests_per_second,
+        resume,
+        &task,
+    )
+    .await;
+    match result {
+        Ok(result) => task.complete(TaskResult::DeleteDuplicates { result }).await,
+        Err(e) => task.fail(e.to_string()).await,
+    }
+}
+
+async fn delete_duplicates_inner(
     client: &ApiClient,
     input_file: &str,
+    batch_size: u32,
     concurrency: usize,
     dry_run: bool,
-) -> Result<DeleteDuplicatesResult, ApiError> {
+    requests_per_second: Option<f64>,
+    resume: bool,
+    task: &TaskHandle,
+) -> Result<DeleteDuplicatesResult, SbomApiError> {
     // Check if file exists
     let path = Path::new(input_file);
     if !path.exists() {
-        return Err(ApiError::InternalError(format!(
-            "Input file not found: {}",
-            input_file
-        )));
+        return Err(SbomApiError::new(
+            "input_file_not_found",
+            ApiErrorType::Io,
+            format!("Input file not found: {}", input_file),
+        ));
     }
 
     // Read and parse the file
-    let file = File::open(path)
-        .map_err(|e| ApiError::InternalError(format!("Failed to open input file: {}", e)))?;
+    let file = File::open(path).map_err(|e| {
+        SbomApiError::new(
+            "input_file_open_failed",
+            ApiErrorType::Io,
+            format!("Failed to open input file: {}", e),
+        )
+    })?;
     let reader = BufReader::new(file);
 
-    let groups: Vec<DuplicateGroup> = serde_json::from_reader(reader)
-        .map_err(|e| ApiError::InternalError(format!("Failed to parse input file: {}", e)))?;
+    let groups: Vec<DuplicateGroup> = serde_json::from_reader(reader).map_err(|e| {
+        SbomApiError::new(
+            "input_file_parse_failed",
+            ApiErrorType::InvalidResponse,
+            format!("Failed to parse input file: {}", e),
+        )
+    })?;
 
     // Collect all duplicate entries to delete
     let entries: Vec<DeleteEntry> = groups
@@ -359,12 +1648,14 @@ pub async fn delete_duplicates(
         .collect();
 
     let total = entries.len() as u32;
+    task.set_total(total as u64).await;
 
     if dry_run {
         for entry in &entries {
-            eprintln!(
+            log::info!(
                 "[DRY-RUN] Would delete: {} (document_id: {})",
-                entry.id, entry.document_id
+                entry.id,
+                entry.document_id
             );
         }
         return Ok(DeleteDuplicatesResult {
@@ -372,61 +1663,370 @@ pub async fn delete_duplicates(
             skipped: 0,
             failed: 0,
             total,
+            retries: 0,
+            throttled: 0,
+            resumed: 0,
         });
     }
 
-    eprintln!(
-        "Deleting {} duplicates with {} concurrent requests...\n",
-        total, concurrency
-    );
+    let journal_path = delete_journal_path(input_file);
+    let already_done = if resume {
+        load_delete_journal(&journal_path)?
+    } else {
+        HashMap::new()
+    };
 
-    // Set up progress bar
-    let progress = ProgressBar::new(total as u64);
-    progress.set_style(
-        ProgressStyle::default_bar()
-            .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} ({percent}%) {msg}")?
-            .progress_chars("█▓░"),
-    );
+    let (resumed_entries, pending_entries): (Vec<DeleteEntry>, Vec<DeleteEntry>) = entries
+        .into_iter()
+        .partition(|entry| already_done.contains_key(&entry.id));
 
-    let deleted = Arc::new(AtomicU32::new(0));
-    let skipped = Arc::new(AtomicU32::new(0));
-    let failed = Arc::new(AtomicU32::new(0));
+    let resumed = resumed_entries.len() as u32;
+    let (mut deleted, mut skipped, mut failed) = (0u32, 0u32, 0u32);
+    for entry in &resumed_entries {
+        match already_done.get(&entry.id) {
+            Some(DeleteOutcome::Deleted) => deleted += 1,
+            Some(DeleteOutcome::Skipped) => skipped += 1,
+            Some(DeleteOutcome::Failed) | None => failed += 1,
+        }
+    }
+    task.inc_processed(resumed as u64).await;
 
-    stream::iter(entries)
-        .for_each_concurrent(concurrency, |entry| {
+    let batch_size = batch_size.max(1) as usize;
+    let chunks: Vec<Vec<DeleteEntry>> = pending_entries
+        .chunks(batch_size)
+        .map(|chunk| chunk.to_vec())
+        .collect();
+    let rate_limiter = requests_per_second.map(|rps| Arc::new(RateLimiter::new(rps)));
+    let journal = Arc::new(Mutex::new(open_delete_journal(&journal_path, resume)?));
+
+    let per_chunk_counts: Vec<(u32, u32, u32)> = stream::iter(chunks)
+        .map(|chunk| {
             let client = client.clone();
-            let deleted = Arc::clone(&deleted);
-            let skipped = Arc::clone(&skipped);
-            let failed = Arc::clone(&failed);
-            let progress = progress.clone();
+            let task = task.clone();
+            let rate_limiter = rate_limiter.clone();
+            let journal = Arc::clone(&journal);
             async move {
-                match delete(&client, &entry.id).await {
-                    Ok(_) => {
-                        deleted.fetch_add(1, Ordering::Relaxed);
+                let chunk_len = chunk.len() as u64;
+
+                if task.is_cancelled() {
+                    task.inc_processed(chunk_len).await;
+                    return (0, 0, 0);
+                }
+
+                if let Some(limiter) = &rate_limiter {
+                    if limiter.acquire().await {
+                        task.inc_throttled(1).await;
                     }
-                    Err(ApiError::NotFound(_)) => {
-                        // SBOM already deleted or doesn't exist - skip silently
-                        skipped.fetch_add(1, Ordering::Relaxed);
+                }
+
+                let ids: Vec<String> = chunk.iter().map(|e| e.id.clone()).collect();
+                let delete_result = with_retry(&task, DEFAULT_MAX_RETRY_ATTEMPTS, || {
+                    delete_batch(&client, &ids)
+                })
+                .await;
+
+                let counts = match delete_result {
+                    Ok(results) => {
+                        let mut deleted = 0;
+                        let mut skipped = 0;
+                        let mut failed = 0;
+
+                        for result in results {
+                            let outcome = match result.status {
+                                BatchDeleteStatus::Deleted => {
+                                    deleted += 1;
+                                    DeleteOutcome::Deleted
+                                }
+                                // SBOM already deleted or doesn't exist - skip silently
+                                BatchDeleteStatus::NotFound => {
+                                    skipped += 1;
+                                    DeleteOutcome::Skipped
+                                }
+                                BatchDeleteStatus::Error => {
+                                    failed += 1;
+                                    log::error!(
+                                        "Failed to delete {}: {}",
+                                        result.id,
+                                        result.message.unwrap_or_default()
+                                    );
+                                    DeleteOutcome::Failed
+                                }
+                            };
+
+                            if let Err(e) =
+                                append_delete_journal_record(&journal, &result.id, outcome).await
+                            {
+                                log::warn!("Failed to checkpoint deletion of {}: {}", result.id, e);
+                            }
+                        }
+
+                        (deleted, skipped, failed)
                     }
                     Err(e) => {
-                        failed.fetch_add(1, Ordering::Relaxed);
-                        progress.println(format!(
-                            "Failed to delete {} (document_id: {}): {}",
-                            entry.id, entry.document_id, e
-                        ));
+                        log::error!("Batch delete of {} entries failed: {}", chunk.len(), e);
+                        for id in &ids {
+                            if let Err(e) =
+                                append_delete_journal_record(&journal, id, DeleteOutcome::Failed)
+                                    .await
+                            {
+                                log::warn!("Failed to checkpoint deletion of {}: {}", id, e);
+                            }
+                        }
+                        (0, 0, chunk.len() as u32)
                     }
-                }
-                progress.inc(1);
+                };
+
+                task.inc_processed(chunk_len).await;
+                counts
             }
         })
+        .buffer_unordered(concurrency)
+        .collect()
         .await;
 
-    progress.finish_with_message("complete");
+    let (new_deleted, new_skipped, new_failed) = per_chunk_counts.into_iter().fold(
+        (0u32, 0u32, 0u32),
+        |(deleted, skipped, failed), (d, s, f)| (deleted + d, skipped + s, failed + f),
+    );
+    deleted += new_deleted;
+    skipped += new_skipped;
+    failed += new_failed;
+
+    let final_status = task.status().await;
 
     Ok(DeleteDuplicatesResult {
-        deleted: deleted.load(Ordering::Relaxed),
-        skipped: skipped.load(Ordering::Relaxed),
-        failed: failed.load(Ordering::Relaxed),
+        deleted,
+        skipped,
+        failed,
         total,
+        retries: final_status.retries as u32,
+        throttled: final_status.throttled as u32,
+        resumed,
     })
 }
+
+/// Register a background task that deletes the duplicates listed in `input_file` and returns
+/// immediately with its task ID. Poll `registry.get_task(id)` for progress and the final
+/// `DeleteDuplicatesResult`. When `resume` is true, ids already recorded as done in a prior
+/// run's progress journal (`{input_file}` with its extension replaced by `.journal.jsonl`) are
+/// skipped instead of resubmitted; when false, any such journal is discarded and a fresh one is
+/// started.
+pub async fn delete_duplicates(
+    client: &ApiClient,
+    input_file: String,
+    batch_size: u32,
+    concurrency: usize,
+    dry_run: bool,
+    requests_per_second: Option<f64>,
+    resume: bool,
+    registry: &TaskRegistry,
+) -> TaskId {
+    let client = client.clone();
+    let task = registry.register().await;
+    let task_id = task.id().to_string();
+
+    tokio::spawn(run_delete_duplicates(
+        client,
+        input_file,
+        batch_size,
+        concurrency,
+        dry_run,
+        requests_per_second,
+        resume,
+        task,
+    ));
+
+    task_id
+}
+
+/// Unique identifier for a background task
+pub type TaskId = String;
+
+/// Lifecycle state of a background task
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskState {
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+/// Outcome of a completed background task, tagged by which operation produced it
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind")]
+pub enum TaskResult {
+    FindDuplicates { groups: Vec<DuplicateGroup> },
+    DeleteDuplicates { result: DeleteDuplicatesResult },
+}
+
+/// Snapshot of a background task's progress, and (once finished) its result or error
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskStatus {
+    pub state: TaskState,
+    pub processed: u64,
+    pub total: u64,
+    /// Number of times a unit of work was retried after a retryable failure
+    pub retries: u64,
+    /// Number of times a unit of work was delayed by the client-side rate limiter
+    pub throttled: u64,
+    pub started_at: DateTime<Utc>,
+    pub result: Option<TaskResult>,
+    pub error: Option<String>,
+}
+
+struct TaskInner {
+    state: TaskState,
+    processed: u64,
+    total: u64,
+    retries: u64,
+    throttled: u64,
+    started_at: DateTime<Utc>,
+    result: Option<TaskResult>,
+    error: Option<String>,
+}
+
+/// Handle to a single background task, shared between the worker that reports progress and the
+/// registry that answers polls. Cloning a handle is cheap; clones observe the same task.
+#[derive(Clone)]
+pub struct TaskHandle {
+    id: TaskId,
+    inner: Arc<Mutex<TaskInner>>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl TaskHandle {
+    fn new(id: TaskId) -> Self {
+        Self {
+            id,
+            inner: Arc::new(Mutex::new(TaskInner {
+                state: TaskState::Running,
+                processed: 0,
+                total: 0,
+                retries: 0,
+                throttled: 0,
+                started_at: Utc::now(),
+                result: None,
+                error: None,
+            })),
+            cancel: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    async fn set_total(&self, total: u64) {
+        self.inner.lock().await.total = total;
+    }
+
+    async fn inc_processed(&self, delta: u64) {
+        self.inner.lock().await.processed += delta;
+    }
+
+    async fn inc_retries(&self, delta: u64) {
+        self.inner.lock().await.retries += delta;
+    }
+
+    async fn inc_throttled(&self, delta: u64) {
+        self.inner.lock().await.throttled += delta;
+    }
+
+    async fn complete(&self, result: TaskResult) {
+        let mut inner = self.inner.lock().await;
+        inner.state = TaskState::Completed;
+        inner.result = Some(result);
+    }
+
+    async fn fail(&self, message: String) {
+        let mut inner = self.inner.lock().await;
+        inner.state = TaskState::Failed;
+        inner.error = Some(message);
+    }
+
+    /// Request that the workers for this task stop at the next checkpoint (e.g. between pages)
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether `cancel()` has been called; workers should check this between units of work
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+
+    async fn status(&self) -> TaskStatus {
+        let inner = self.inner.lock().await;
+        let state = if self.is_cancelled() && inner.state == TaskState::Running {
+            TaskState::Cancelled
+        } else {
+            inner.state
+        };
+        TaskStatus {
+            state,
+            processed: inner.processed,
+            total: inner.total,
+            retries: inner.retries,
+            throttled: inner.throttled,
+            started_at: inner.started_at,
+            result: inner.result.clone(),
+            error: inner.error.clone(),
+        }
+    }
+}
+
+/// Registry of in-flight and completed background tasks. Lets a long-running scan or deletion
+/// sweep run once on a server and be monitored by multiple clients instead of blocking the
+/// caller that started it.
+#[derive(Clone, Default)]
+pub struct TaskRegistry {
+    tasks: Arc<RwLock<HashMap<TaskId, TaskHandle>>>,
+}
+
+impl TaskRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    async fn register(&self) -> TaskHandle {
+        let handle = TaskHandle::new(Uuid::new_v4().to_string());
+        self.tasks
+            .write()
+            .await
+            .insert(handle.id().to_string(), handle.clone());
+        handle
+    }
+
+    /// Look up a task's current status by ID, so callers can poll progress instead of blocking
+    /// on the operation itself. Returns `None` if no task with `id` is registered.
+    pub async fn get_task(&self, id: &str) -> Option<TaskStatus> {
+        let tasks = self.tasks.read().await;
+        match tasks.get(id) {
+            Some(handle) => Some(handle.status().await),
+            None => None,
+        }
+    }
+
+    /// Request cancellation of a running task; the workers stop at the next checkpoint rather
+    /// than immediately.
+    pub async fn cancel_task(&self, id: &str) -> bool {
+        let tasks = self.tasks.read().await;
+        match tasks.get(id) {
+            Some(handle) => {
+                handle.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// List every known task (running or finished) with its current status
+    pub async fn list_tasks(&self) -> Vec<(TaskId, TaskStatus)> {
+        let tasks = self.tasks.read().await;
+        let mut out = Vec::with_capacity(tasks.len());
+        for (id, handle) in tasks.iter() {
+            out.push((id.clone(), handle.status().await));
+        }
+        out
+    }
+}
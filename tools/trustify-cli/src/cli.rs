@@ -0,0 +1,23 @@
+use clap::Parser;
+
+use crate::commands::Commands;
+use crate::commands::ColorMode;
+use crate::config::Config;
+
+/// Trustify CLI - Software Supply-Chain Security tool
+#[derive(Parser)]
+#[command(name = "trustify")]
+#[command(about = "CLI for interacting with the Trustify API", long_about = None)]
+#[command(version)]
+pub struct Cli {
+    #[command(flatten)]
+    pub config: Config,
+
+    /// Colorize and pretty-print JSON output: auto, always, never (default: auto). Applies to
+    /// every subcommand that emits JSON, not just the one it's passed before.
+    #[arg(long, value_enum, default_value = "auto", global = true)]
+    pub color: ColorMode,
+
+    #[command(subcommand)]
+    pub command: Commands,
+}
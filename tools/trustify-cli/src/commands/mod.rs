@@ -0,0 +1,41 @@
+pub mod sbom;
+
+use clap::{CommandFactory, Subcommand};
+use clap_complete::Shell;
+
+use crate::Context;
+use crate::cli::Cli;
+pub use sbom::{ColorMode, SbomCommands};
+
+#[derive(Subcommand)]
+pub enum Commands {
+    /// SBOM management commands
+    Sbom {
+        #[command(subcommand)]
+        command: SbomCommands,
+    },
+
+    /// Generate shell completions for this CLI
+    Completions {
+        /// Shell to generate completions for
+        shell: Shell,
+    },
+}
+
+impl Commands {
+    pub async fn run(&self, ctx: &Context) {
+        match self {
+            Commands::Sbom { command } => command.run(ctx).await,
+            Commands::Completions { shell } => generate_completions(*shell),
+        }
+    }
+}
+
+/// Generate a shell completion script for the full `Cli` command tree and write it to stdout.
+/// Covers every subcommand under `sbom` - including `duplicates find`/`delete`, `--format`
+/// table/csv, `--sort`, and `--color` - since they all live in this crate's command tree.
+fn generate_completions(shell: Shell) {
+    let mut cmd = Cli::command();
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, name, &mut std::io::stdout());
+}
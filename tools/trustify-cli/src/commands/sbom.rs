@@ -1,13 +1,41 @@
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 use std::path::Path;
 use std::process;
+use std::time::Duration;
 
 use clap::{Subcommand, ValueEnum};
 use serde_json::Value;
 
-use crate::api::sbom as sbom_api;
+use crate::api::sbom::{self as sbom_api, TaskResult, TaskState, TaskStatus};
 use crate::Context;
 
+/// Poll interval while waiting on a background task started by the duplicates commands
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Poll `task_id` until it reaches a terminal state, printing progress as it goes
+async fn await_task(ctx: &Context, task_id: &str) -> TaskStatus {
+    loop {
+        let status = ctx
+            .tasks
+            .get_task(task_id)
+            .await
+            .expect("task was just registered");
+
+        eprint!(
+            "\rtask {}: {:?} ({}/{}, {} retries, {} throttled)   ",
+            task_id, status.state, status.processed, status.total, status.retries, status.throttled
+        );
+        io::stderr().flush().ok();
+
+        if status.state != TaskState::Running {
+            eprintln!();
+            return status;
+        }
+
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
 /// Output format for SBOM list
 #[derive(Clone, Default, ValueEnum)]
 pub enum ListFormat {
@@ -20,6 +48,123 @@ pub enum ListFormat {
     /// Output complete JSON document
     #[default]
     Full,
+    /// Output id, name, document_id, ingested, published, size as aligned columns
+    Table,
+    /// Output id, name, document_id, ingested, published, size as RFC-4180 CSV
+    Csv,
+}
+
+/// Controls whether JSON output is syntax-highlighted and indented
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum ColorMode {
+    /// Colorize when stdout is a TTY, stay compact and plain otherwise
+    #[default]
+    Auto,
+    /// Always colorize and pretty-print
+    Always,
+    /// Never colorize; emit compact, plain JSON
+    Never,
+}
+
+impl ColorMode {
+    /// Whether colorized, pretty-printed output should be used for the current process
+    fn enabled(self) -> bool {
+        match self {
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+            ColorMode::Auto => io::stdout().is_terminal(),
+        }
+    }
+}
+
+mod ansi {
+    pub const RESET: &str = "\x1b[0m";
+    pub const KEY: &str = "\x1b[36m";
+    pub const STRING: &str = "\x1b[32m";
+    pub const NUMBER: &str = "\x1b[33m";
+    pub const KEYWORD: &str = "\x1b[35m";
+}
+
+/// Render a JSON value as a single string, optionally indented and syntax-highlighted.
+///
+/// `pretty` controls indentation; `color` controls whether ANSI color codes are emitted.
+/// Passing `color: ColorMode::Never` with `pretty: true` yields plain indented JSON, while
+/// `pretty: false` always yields compact JSON regardless of `color` (there's nothing to
+/// indent, and no highlighting is worth doing for a one-line blob).
+fn render_json(value: &Value, color: ColorMode, pretty: bool) -> String {
+    if !pretty {
+        return serde_json::to_string(value).unwrap_or_default();
+    }
+
+    if !color.enabled() {
+        return serde_json::to_string_pretty(value).unwrap_or_default();
+    }
+
+    let mut out = String::new();
+    render_colored(value, 0, &mut out);
+    out
+}
+
+/// Recursively append a colorized, indented rendering of `value` to `out`
+fn render_colored(value: &Value, indent: usize, out: &mut String) {
+    let pad = "  ".repeat(indent);
+    let child_pad = "  ".repeat(indent + 1);
+
+    match value {
+        Value::Object(map) => {
+            if map.is_empty() {
+                out.push_str("{}");
+                return;
+            }
+            out.push_str("{\n");
+            for (i, (key, val)) in map.iter().enumerate() {
+                out.push_str(&child_pad);
+                out.push_str(ansi::KEY);
+                out.push_str(&serde_json::to_string(key).unwrap_or_default());
+                out.push_str(ansi::RESET);
+                out.push_str(": ");
+                render_colored(val, indent + 1, out);
+                if i + 1 < map.len() {
+                    out.push(',');
+                }
+                out.push('\n');
+            }
+            out.push_str(&pad);
+            out.push('}');
+        }
+        Value::Array(items) => {
+            if items.is_empty() {
+                out.push_str("[]");
+                return;
+            }
+            out.push_str("[\n");
+            for (i, val) in items.iter().enumerate() {
+                out.push_str(&child_pad);
+                render_colored(val, indent + 1, out);
+                if i + 1 < items.len() {
+                    out.push(',');
+                }
+                out.push('\n');
+            }
+            out.push_str(&pad);
+            out.push(']');
+        }
+        Value::String(_) => {
+            out.push_str(ansi::STRING);
+            out.push_str(&value.to_string());
+            out.push_str(ansi::RESET);
+        }
+        Value::Number(_) => {
+            out.push_str(ansi::NUMBER);
+            out.push_str(&value.to_string());
+            out.push_str(ansi::RESET);
+        }
+        Value::Bool(_) | Value::Null => {
+            out.push_str(ansi::KEYWORD);
+            out.push_str(&value.to_string());
+            out.push_str(ansi::RESET);
+        }
+    }
 }
 
 #[derive(Subcommand)]
@@ -37,6 +182,20 @@ pub enum DuplicatesCommands {
         /// Output file
         #[arg(long, default_value = "duplicates.json")]
         output: Option<String>,
+
+        /// Resume from a prior scan's checkpoint journal instead of starting a fresh one
+        #[arg(long)]
+        resume_from: Option<String>,
+
+        /// Group SBOMs whose component/purl sets are near-duplicates (estimated Jaccard
+        /// similarity at or above this threshold, 0.0-1.0) instead of requiring a
+        /// byte-identical document_id
+        #[arg(long)]
+        similarity_threshold: Option<f64>,
+
+        /// Cap on requests per second shared across all fetch workers (default: unlimited)
+        #[arg(long)]
+        requests_per_second: Option<f64>,
     },
     /// Delete duplicates
     Delete {
@@ -44,13 +203,29 @@ pub enum DuplicatesCommands {
         #[arg(long, default_value = "duplicates.json")]
         input: Option<String>,
 
-        /// Number of concurrent delete requests (default: 8)
+        /// Number of IDs submitted per batch-delete request
+        #[arg(short = 'b', long, default_value_t = sbom_api::DEFAULT_DELETE_BATCH_SIZE)]
+        batch_size: u32,
+
+        /// Number of concurrent batch-delete requests (default: 8)
         #[arg(short = 'j', long, default_value = "8")]
         concurrency: usize,
 
         /// Perform a dry run without actually deleting
         #[arg(long)]
         dry_run: bool,
+
+        /// Cap on requests per second shared across all delete workers (default: unlimited)
+        #[arg(long)]
+        requests_per_second: Option<f64>,
+
+        /// Resume a prior run using its progress journal, skipping ids already recorded as done
+        #[arg(long, conflicts_with = "fresh")]
+        resume: bool,
+
+        /// Discard any existing progress journal and start a fresh run (default)
+        #[arg(long, conflicts_with = "resume")]
+        fresh: bool,
     },
 }
 
@@ -76,7 +251,7 @@ pub enum SbomCommands {
         /// Example: `purl:qualifiers:type:desc`
         #[arg(long)]
         sort: Option<String>,
-        /// Output format: id, name, short, full (default: full)
+        /// Output format: id, name, short, full, table, csv (default: full)
         #[arg(long, value_enum, default_value = "full")]
         format: ListFormat,
     },
@@ -90,6 +265,10 @@ pub enum SbomCommands {
         #[arg(long)]
         query: Option<String>,
 
+        /// Number of concurrent delete requests (default: 8)
+        #[arg(short = 'j', long, default_value = "8")]
+        concurrency: usize,
+
         /// Perform a dry run without actually deleting
         #[arg(long)]
         dry_run: bool,
@@ -99,6 +278,38 @@ pub enum SbomCommands {
         #[command(subcommand)]
         command: DuplicatesCommands,
     },
+    /// Export SBOMs to disk for migration between instances
+    Export {
+        /// SBOM ID to export
+        #[arg(long)]
+        id: Option<String>,
+
+        /// Query filter for SBOMs to export
+        #[arg(long)]
+        query: Option<String>,
+
+        /// Directory to write exported documents into (or the archive path, with --archive)
+        #[arg(long)]
+        output_dir: String,
+
+        /// Write a single gzipped tar archive at `output_dir` instead of one file per document
+        #[arg(long)]
+        archive: bool,
+
+        /// Number of concurrent fetch requests (default: 8)
+        #[arg(short = 'j', long, default_value = "8")]
+        concurrency: usize,
+    },
+    /// Import SBOMs previously exported with `sbom export`
+    Import {
+        /// Directory of documents, or a gzipped tar archive, to import
+        #[arg(long)]
+        input_dir: String,
+
+        /// Number of concurrent upload requests (default: 8)
+        #[arg(short = 'j', long, default_value = "8")]
+        concurrency: usize,
+    },
 }
 
 impl SbomCommands {
@@ -108,7 +319,13 @@ impl SbomCommands {
                 command.run(ctx).await;
             }
             SbomCommands::Get { id } => match sbom_api::get(&ctx.client, id).await {
-                Ok(json) => println!("{}", json),
+                Ok(json) => match serde_json::from_str::<Value>(&json) {
+                    Ok(parsed) => {
+                        let pretty = ctx.color.enabled();
+                        println!("{}", render_json(&parsed, ctx.color, pretty));
+                    }
+                    Err(_) => println!("{}", json),
+                },
                 Err(e) => {
                     eprintln!("Error: {}", e);
                     process::exit(1);
@@ -129,7 +346,7 @@ impl SbomCommands {
                 };
                 match sbom_api::list(&ctx.client, &params).await {
                     Ok(json) => {
-                        format_list_output(&json, format);
+                        format_list_output(&json, format, ctx.color);
                     }
                     Err(e) => {
                         eprintln!("Error: {}", e);
@@ -137,18 +354,115 @@ impl SbomCommands {
                     }
                 }
             }
-            SbomCommands::Delete { id, query, dry_run } => {
-                println!(
-                    "SBOM delete command executed successfully!{}",
-                    if *dry_run { " (dry-run)" } else { "" }
-                );
-                if let Some(i) = id {
-                    println!("  ID: {}", i);
+            SbomCommands::Delete {
+                id,
+                query,
+                concurrency,
+                dry_run,
+            } => {
+                if id.is_none() && query.is_none() {
+                    eprintln!("Error: one of --id or --query is required");
+                    process::exit(1);
                 }
-                if let Some(q) = query {
-                    println!("  Query: {}", q);
+
+                match sbom_api::delete_by_query(
+                    &ctx.client,
+                    query.as_deref(),
+                    *dry_run,
+                    *concurrency,
+                    id.as_deref(),
+                )
+                .await
+                {
+                    Ok(result) => {
+                        if *dry_run {
+                            println!("[DRY-RUN] Would delete {} SBOM(s)", result.total);
+                        } else {
+                            let mut msg = format!("Deleted {} SBOM(s)", result.deleted);
+                            if result.skipped > 0 {
+                                msg.push_str(&format!(", {} skipped (not found)", result.skipped));
+                            }
+                            if result.failed > 0 {
+                                msg.push_str(&format!(", {} failed", result.failed));
+                            }
+                            msg.push_str(&format!(" out of {} total", result.total));
+                            println!("{}", msg);
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("Error: {}", e);
+                        process::exit(1);
+                    }
                 }
             }
+            SbomCommands::Export {
+                id,
+                query,
+                output_dir,
+                archive,
+                concurrency,
+            } => {
+                if id.is_none() && query.is_none() {
+                    eprintln!("Error: one of --id or --query is required");
+                    process::exit(1);
+                }
+
+                match sbom_api::export_sboms(
+                    &ctx.client,
+                    query.as_deref(),
+                    id.as_deref(),
+                    output_dir,
+                    *archive,
+                    *concurrency,
+                )
+                .await
+                {
+                    Ok(result) => {
+                        let mut msg = format!("Exported {} SBOM(s)", result.exported);
+                        if result.failed > 0 {
+                            msg.push_str(&format!(", {} failed", result.failed));
+                        }
+                        msg.push_str(&format!(" out of {} total", result.total));
+                        println!("{}", msg);
+                        if result.failed > 0 {
+                            process::exit(1);
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("Error: {}", e);
+                        process::exit(1);
+                    }
+                }
+            }
+            SbomCommands::Import {
+                input_dir,
+                concurrency,
+            } => match sbom_api::import_sboms(&ctx.client, input_dir, *concurrency).await {
+                Ok(result) => {
+                    for outcome in &result.outcomes {
+                        if outcome.success {
+                            println!("OK    {}", outcome.name);
+                        } else {
+                            println!(
+                                "FAILED {} ({})",
+                                outcome.name,
+                                outcome.error.as_deref().unwrap_or("unknown error")
+                            );
+                        }
+                    }
+                    println!(
+                        "Imported {} of {} document(s), {} failed",
+                        result.imported, result.total, result.failed
+                    );
+                    if result.failed > 0 {
+                        process::exit(1);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    process::exit(1);
+                }
+            },
         }
     }
 }
@@ -160,28 +474,47 @@ impl DuplicatesCommands {
                 batch_size,
                 concurrency,
                 output,
+                resume_from,
+                similarity_threshold,
+                requests_per_second,
             } => {
                 let output_path = output
                     .as_ref()
                     .map(|s| s.as_str())
                     .unwrap_or("duplicates.json");
 
-                // Check if output file exists
-                let final_output = check_output_file(output_path);
-                if final_output.is_none() {
-                    eprintln!("Operation cancelled.");
-                    process::exit(0);
-                }
-                let final_output = final_output.unwrap();
+                // Resuming reuses the prior scan's output path; only prompt about overwriting
+                // when starting a fresh scan.
+                let final_output = if resume_from.is_some() {
+                    output_path.to_string()
+                } else {
+                    match check_output_file(output_path) {
+                        Some(path) => path,
+                        None => {
+                            eprintln!("Operation cancelled.");
+                            process::exit(0);
+                        }
+                    }
+                };
 
                 let params = sbom_api::FindDuplicatesParams {
                     batch_size: *batch_size,
                     concurrency: *concurrency,
+                    resume_from: resume_from.clone(),
+                    similarity_threshold: *similarity_threshold,
+                    requests_per_second: *requests_per_second,
                 };
-                match sbom_api::find_duplicates(&ctx.client, &params, &Some(final_output.clone()))
-                    .await
-                {
-                    Ok(groups) => {
+                let task_id = sbom_api::find_duplicates(
+                    &ctx.client,
+                    params,
+                    Some(final_output.clone()),
+                    &ctx.tasks,
+                )
+                .await;
+
+                let status = await_task(ctx, &task_id).await;
+                match status.result {
+                    Some(TaskResult::FindDuplicates { groups }) => {
                         let total_duplicates: usize =
                             groups.iter().map(|g| g.duplicates.len()).sum();
                         println!(
@@ -191,30 +524,57 @@ impl DuplicatesCommands {
                             final_output
                         );
                     }
-                    Err(e) => {
-                        eprintln!("Error: {}", e);
+                    _ => {
+                        eprintln!(
+                            "Error: {}",
+                            status
+                                .error
+                                .unwrap_or_else(|| "task did not complete".to_string())
+                        );
                         process::exit(1);
                     }
                 }
             }
             DuplicatesCommands::Delete {
                 input,
+                batch_size,
                 concurrency,
                 dry_run,
+                requests_per_second,
+                resume,
+                fresh: _,
             } => {
                 let input_path = input
                     .as_ref()
                     .map(|s| s.as_str())
-                    .unwrap_or("duplicates.json");
+                    .unwrap_or("duplicates.json")
+                    .to_string();
 
-                match sbom_api::delete_duplicates(&ctx.client, input_path, *concurrency, *dry_run)
-                    .await
-                {
-                    Ok(result) => {
+                let task_id = sbom_api::delete_duplicates(
+                    &ctx.client,
+                    input_path,
+                    *batch_size,
+                    *concurrency,
+                    *dry_run,
+                    *requests_per_second,
+                    *resume,
+                    &ctx.tasks,
+                )
+                .await;
+
+                let status = await_task(ctx, &task_id).await;
+                match status.result {
+                    Some(TaskResult::DeleteDuplicates { result }) => {
                         if *dry_run {
                             println!("[DRY-RUN] Would delete {} duplicate(s)", result.total);
                         } else {
                             let mut msg = format!("Deleted {} duplicate(s)", result.deleted);
+                            if result.resumed > 0 {
+                                msg.push_str(&format!(
+                                    " ({} resumed from journal)",
+                                    result.resumed
+                                ));
+                            }
                             if result.skipped > 0 {
                                 msg.push_str(&format!(", {} skipped (not found)", result.skipped));
                             }
@@ -222,11 +582,25 @@ impl DuplicatesCommands {
                                 msg.push_str(&format!(", {} failed", result.failed));
                             }
                             msg.push_str(&format!(" out of {} total", result.total));
+                            if result.retries > 0 {
+                                msg.push_str(&format!(" ({} retries", result.retries));
+                                if result.throttled > 0 {
+                                    msg.push_str(&format!(", {} throttled", result.throttled));
+                                }
+                                msg.push(')');
+                            } else if result.throttled > 0 {
+                                msg.push_str(&format!(" ({} throttled)", result.throttled));
+                            }
                             println!("{}", msg);
                         }
                     }
-                    Err(e) => {
-                        eprintln!("Error: {}", e);
+                    _ => {
+                        eprintln!(
+                            "Error: {}",
+                            status
+                                .error
+                                .unwrap_or_else(|| "task did not complete".to_string())
+                        );
                         process::exit(1);
                     }
                 }
@@ -236,7 +610,7 @@ impl DuplicatesCommands {
 }
 
 /// Format and print list output based on the specified format
-fn format_list_output(json: &str, format: &ListFormat) {
+fn format_list_output(json: &str, format: &ListFormat, color: ColorMode) {
     let parsed: Value = match serde_json::from_str(json) {
         Ok(v) => v,
         Err(e) => {
@@ -255,9 +629,11 @@ fn format_list_output(json: &str, format: &ListFormat) {
         }
     };
 
+    let pretty = color.enabled();
+
     match format {
         ListFormat::Full => {
-            println!("{}", json);
+            println!("{}", render_json(&parsed, color, pretty));
         }
         ListFormat::Id => {
             for item in items {
@@ -277,7 +653,7 @@ fn format_list_output(json: &str, format: &ListFormat) {
                     })
                 })
                 .collect();
-            println!("{}", serde_json::to_string(&result).unwrap_or_default());
+            println!("{}", render_json(&Value::Array(result), color, pretty));
         }
         ListFormat::Short => {
             let result: Vec<Value> = items
@@ -293,11 +669,89 @@ fn format_list_output(json: &str, format: &ListFormat) {
                     })
                 })
                 .collect();
-            println!("{}", serde_json::to_string(&result).unwrap_or_default());
+            println!("{}", render_json(&Value::Array(result), color, pretty));
+        }
+        ListFormat::Table => {
+            print!("{}", render_table(items, TABLE_COLUMNS));
+        }
+        ListFormat::Csv => {
+            print!("{}", render_csv(items, TABLE_COLUMNS));
         }
     }
 }
 
+/// Column set shared by the `Short`, `Table`, and `Csv` formats
+const TABLE_COLUMNS: &[&str] = &["id", "name", "document_id", "ingested", "published", "size"];
+
+/// Render a JSON value as a plain display string for a table/CSV cell: strings are unquoted,
+/// other scalars use their JSON representation, and missing/null fields render as empty
+fn cell_value(item: &Value, column: &str) -> String {
+    match item.get(column) {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+/// Render `items` as aligned columns with a header row, sized to the widest cell in each column
+fn render_table(items: &[Value], columns: &[&str]) -> String {
+    let rows: Vec<Vec<String>> = items
+        .iter()
+        .map(|item| columns.iter().map(|c| cell_value(item, c)).collect())
+        .collect();
+
+    let widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            rows.iter()
+                .map(|row| row[i].len())
+                .max()
+                .unwrap_or(0)
+                .max(c.len())
+        })
+        .collect();
+
+    let mut out = String::new();
+    for (i, c) in columns.iter().enumerate() {
+        out.push_str(&format!("{:width$}  ", c.to_uppercase(), width = widths[i]));
+    }
+    out.push('\n');
+    for row in &rows {
+        for (i, cell) in row.iter().enumerate() {
+            out.push_str(&format!("{:width$}  ", cell, width = widths[i]));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Quote a CSV field per RFC 4180: wrap in quotes and double any embedded quotes whenever the
+/// field contains a comma, quote, or newline
+fn csv_quote(field: &str) -> String {
+    if field.contains(',') || field.contains('"') || field.contains('\n') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Render `items` as RFC-4180 CSV with a header row
+fn render_csv(items: &[Value], columns: &[&str]) -> String {
+    let mut out = String::new();
+    out.push_str(&columns.join(","));
+    out.push_str("\r\n");
+    for item in items {
+        let row: Vec<String> = columns
+            .iter()
+            .map(|c| csv_quote(&cell_value(item, c)))
+            .collect();
+        out.push_str(&row.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
 /// Check if output file exists and prompt user for action
 /// Returns None if user cancels, Some(path) with the final path to use
 fn check_output_file(output_path: &str) -> Option<String> {
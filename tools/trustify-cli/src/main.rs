@@ -9,12 +9,18 @@ use clap::Parser;
 
 use api::auth::AuthCredentials;
 use api::ApiClient;
+use api::sbom::TaskRegistry;
 use cli::Cli;
+use commands::ColorMode;
 
-/// Runtime context containing config and API client
+/// Runtime context containing config, API client, the registry of background tasks
+/// (e.g. duplicate scans/deletions) started during this process's lifetime, and the global
+/// `--color` setting every JSON-emitting subcommand renders with
 pub struct Context {
     pub config: config::Config,
     pub client: ApiClient,
+    pub tasks: TaskRegistry,
+    pub color: ColorMode,
 }
 
 #[tokio::main]
@@ -46,6 +52,8 @@ async fn main() {
     let ctx = Context {
         config: cli.config,
         client,
+        tasks: TaskRegistry::new(),
+        color: cli.color,
     };
 
     cli.command.run(&ctx).await;